@@ -0,0 +1,55 @@
+use std::fmt;
+
+use crate::cartridge::header::CartridgeType;
+
+/// Problems with a ROM image, found before it ever gets to run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CartridgeError {
+    /// The image ends before the 0x0100-0x014F header does.
+    MissingHeader { len: usize },
+    /// The ROM size code in the header isn't one we know of.
+    UnknownRomSize { code: u8 },
+    /// The image is smaller than the header says the ROM is.
+    Truncated { expected: usize, actual: usize },
+    /// The image is bigger than the header says the ROM is.
+    Oversized { expected: usize, actual: usize },
+    /// The boot ROM would refuse to start this cartridge.
+    BadHeaderChecksum { expected: u8, computed: u8 },
+    /// The cartridge needs hardware that isn't emulated.
+    UnsupportedCartridgeType(CartridgeType),
+}
+
+impl fmt::Display for CartridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CartridgeError::MissingHeader { len } => {
+                write!(f, "ROM is only {} bytes, too short for a header", len)
+            }
+            CartridgeError::UnknownRomSize { code } => {
+                write!(f, "unknown ROM size code ${:02X}", code)
+            }
+            CartridgeError::Truncated { expected, actual } => write!(
+                f,
+                "ROM is truncated, {} bytes of the expected {}",
+                actual, expected
+            ),
+            CartridgeError::Oversized { expected, actual } => write!(
+                f,
+                "ROM is {} bytes, more than the expected {}",
+                actual, expected
+            ),
+            CartridgeError::BadHeaderChecksum { expected, computed } => write!(
+                f,
+                "bad header checksum ${:02X}, computed ${:02X}",
+                expected, computed
+            ),
+            CartridgeError::UnsupportedCartridgeType(cartridge_type) => write!(
+                f,
+                "unsupported cartridge type ${:02X} {}",
+                cartridge_type.code, cartridge_type
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CartridgeError {}
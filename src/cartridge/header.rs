@@ -0,0 +1,366 @@
+use std::fmt;
+
+use crate::cartridge::error::CartridgeError;
+use crate::cpu::concat_bytes;
+
+pub const HEADER_END: usize = 0x0150;
+
+/// The cartridge header, found at 0x0100-0x014F of every ROM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Header {
+    pub title: String,
+    // Only in newer cartridges, which take it out of the end of the title
+    pub manufacturer: Option<String>,
+    pub cgb: CgbSupport,
+    pub sgb: bool,
+    pub cartridge_type: CartridgeType,
+    pub rom_size_code: u8,
+    pub ram_size_code: u8,
+    pub licensee: Licensee,
+    pub version: u8,
+    pub header_checksum: Checksum,
+    pub global_checksum: Checksum,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CgbSupport {
+    // DMG only
+    None,
+    // Runs on both, with CGB extras
+    Enhanced,
+    Only,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Licensee {
+    // 0x014B
+    Old(u8),
+    // 0x0144-0x0145, used when the old code is 0x33
+    New(String),
+}
+
+/// A checksum as stored in the header, next to the one computed from the
+/// ROM itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checksum {
+    pub expected: u16,
+    pub computed: u16,
+}
+
+impl Checksum {
+    pub fn is_valid(&self) -> bool {
+        self.expected == self.computed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mbc {
+    None,
+    Mbc1,
+    Mbc2,
+    Mbc3,
+    Mbc5,
+    Mbc6,
+    Mbc7,
+    Mmm01,
+    PocketCamera,
+    Tama5,
+    HuC1,
+    HuC3,
+    Unknown,
+}
+
+/// Byte 0x0147, which tells what hardware the cartridge has besides ROM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CartridgeType {
+    pub code: u8,
+    pub mbc: Mbc,
+    pub ram: bool,
+    pub battery: bool,
+    pub timer: bool,
+    pub rumble: bool,
+}
+
+impl CartridgeType {
+    pub fn from_code(code: u8) -> CartridgeType {
+        let (mbc, ram, battery, timer, rumble) = match code {
+            0x00 => (Mbc::None, false, false, false, false),
+            0x01 => (Mbc::Mbc1, false, false, false, false),
+            0x02 => (Mbc::Mbc1, true, false, false, false),
+            0x03 => (Mbc::Mbc1, true, true, false, false),
+            0x05 => (Mbc::Mbc2, false, false, false, false),
+            0x06 => (Mbc::Mbc2, false, true, false, false),
+            0x08 => (Mbc::None, true, false, false, false),
+            0x09 => (Mbc::None, true, true, false, false),
+            0x0B => (Mbc::Mmm01, false, false, false, false),
+            0x0C => (Mbc::Mmm01, true, false, false, false),
+            0x0D => (Mbc::Mmm01, true, true, false, false),
+            0x0F => (Mbc::Mbc3, false, true, true, false),
+            0x10 => (Mbc::Mbc3, true, true, true, false),
+            0x11 => (Mbc::Mbc3, false, false, false, false),
+            0x12 => (Mbc::Mbc3, true, false, false, false),
+            0x13 => (Mbc::Mbc3, true, true, false, false),
+            0x19 => (Mbc::Mbc5, false, false, false, false),
+            0x1A => (Mbc::Mbc5, true, false, false, false),
+            0x1B => (Mbc::Mbc5, true, true, false, false),
+            0x1C => (Mbc::Mbc5, false, false, false, true),
+            0x1D => (Mbc::Mbc5, true, false, false, true),
+            0x1E => (Mbc::Mbc5, true, true, false, true),
+            0x20 => (Mbc::Mbc6, false, false, false, false),
+            0x22 => (Mbc::Mbc7, true, true, false, true),
+            0xFC => (Mbc::PocketCamera, false, false, false, false),
+            0xFD => (Mbc::Tama5, false, false, false, false),
+            0xFE => (Mbc::HuC3, false, false, false, false),
+            0xFF => (Mbc::HuC1, true, true, false, false),
+            _ => (Mbc::Unknown, false, false, false, false),
+        };
+        CartridgeType {
+            code,
+            mbc,
+            ram,
+            battery,
+            timer,
+            rumble,
+        }
+    }
+}
+
+// Pan Docs naming, e.g. MBC3+TIMER+RAM+BATTERY
+impl fmt::Display for CartridgeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.mbc {
+            Mbc::None if !self.ram => return write!(f, "ROM ONLY"),
+            Mbc::None => "ROM",
+            Mbc::Mbc1 => "MBC1",
+            Mbc::Mbc2 => "MBC2",
+            Mbc::Mbc3 => "MBC3",
+            Mbc::Mbc5 => "MBC5",
+            Mbc::Mbc6 => "MBC6",
+            Mbc::Mbc7 => "MBC7+SENSOR",
+            Mbc::Mmm01 => "MMM01",
+            Mbc::PocketCamera => "POCKET CAMERA",
+            Mbc::Tama5 => "BANDAI TAMA5",
+            Mbc::HuC1 => "HuC1",
+            Mbc::HuC3 => "HuC3",
+            Mbc::Unknown => "UNKNOWN",
+        };
+        write!(f, "{}", name)?;
+        for (present, extra) in [
+            (self.timer, "+TIMER"),
+            (self.rumble, "+RUMBLE"),
+            (self.ram, "+RAM"),
+            (self.battery, "+BATTERY"),
+        ] {
+            if present {
+                write!(f, "{}", extra)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Header {
+    pub fn parse(rom: &[u8]) -> Result<Header, CartridgeError> {
+        if rom.len() < HEADER_END {
+            return Err(CartridgeError::MissingHeader { len: rom.len() });
+        }
+
+        let cgb = match rom[0x0143] {
+            0x80 => CgbSupport::Enhanced,
+            0xC0 => CgbSupport::Only,
+            _ => CgbSupport::None,
+        };
+        // Newer cartridges cut the title down to 11 characters to make room
+        // for the manufacturer code and the CGB flag
+        let (title, manufacturer) = match cgb {
+            CgbSupport::None => (text(&rom[0x0134..0x0144]), None),
+            _ => {
+                let manufacturer = &rom[0x013F..0x0143];
+                let manufacturer = if manufacturer.iter().all(u8::is_ascii_alphanumeric) {
+                    Some(text(manufacturer))
+                } else {
+                    None
+                };
+                (text(&rom[0x0134..0x013F]), manufacturer)
+            }
+        };
+        let licensee = match rom[0x014B] {
+            0x33 => Licensee::New(text(&rom[0x0144..0x0146])),
+            code => Licensee::Old(code),
+        };
+
+        Ok(Header {
+            title,
+            manufacturer,
+            cgb,
+            sgb: rom[0x0146] == 0x03,
+            cartridge_type: CartridgeType::from_code(rom[0x0147]),
+            rom_size_code: rom[0x0148],
+            ram_size_code: rom[0x0149],
+            licensee,
+            version: rom[0x014C],
+            header_checksum: Checksum {
+                expected: rom[0x014D] as u16,
+                computed: header_checksum(rom) as u16,
+            },
+            global_checksum: Checksum {
+                // The only big endian value in the header
+                expected: concat_bytes(rom[0x014E], rom[0x014F]),
+                computed: global_checksum(rom),
+            },
+        })
+    }
+
+    /// ROM size in bytes, 32 KiB doubled for every step of the size code.
+    pub fn rom_size(&self) -> Option<usize> {
+        match self.rom_size_code {
+            0x00..=0x08 => Some(0x8000 << self.rom_size_code),
+            _ => None,
+        }
+    }
+
+    /// External RAM size in bytes. MBC2 has its RAM built in, so it reports
+    /// none here.
+    pub fn ram_size(&self) -> Option<usize> {
+        match self.ram_size_code {
+            0x00 => Some(0),
+            // Listed in some docs, but never used by a real cartridge
+            0x01 => Some(0x0800),
+            0x02 => Some(0x2000),
+            0x03 => Some(0x8000),
+            0x04 => Some(0x20000),
+            0x05 => Some(0x10000),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = |size: Option<usize>| match size {
+            Some(0) => "none".to_string(),
+            Some(bytes) => format!("{} KiB", bytes / 1024),
+            None => "unknown".to_string(),
+        };
+        let checksum = |checksum: &Checksum, width: usize| {
+            if checksum.is_valid() {
+                format!("${:0w$X} (ok)", checksum.expected, w = width)
+            } else {
+                format!(
+                    "${:0w$X} (computed ${:0w$X})",
+                    checksum.expected,
+                    checksum.computed,
+                    w = width
+                )
+            }
+        };
+
+        writeln!(f, "Title:            {}", self.title)?;
+        writeln!(
+            f,
+            "Manufacturer:     {}",
+            self.manufacturer.as_deref().unwrap_or("-")
+        )?;
+        let cgb = match self.cgb {
+            CgbSupport::None => "no",
+            CgbSupport::Enhanced => "supported",
+            CgbSupport::Only => "required",
+        };
+        writeln!(f, "CGB:              {}", cgb)?;
+        writeln!(
+            f,
+            "SGB:              {}",
+            if self.sgb { "yes" } else { "no" }
+        )?;
+        writeln!(
+            f,
+            "Cartridge type:   ${:02X} {}",
+            self.cartridge_type.code, self.cartridge_type
+        )?;
+        writeln!(
+            f,
+            "ROM size:         ${:02X} {}",
+            self.rom_size_code,
+            size(self.rom_size())
+        )?;
+        writeln!(
+            f,
+            "RAM size:         ${:02X} {}",
+            self.ram_size_code,
+            size(self.ram_size())
+        )?;
+        match &self.licensee {
+            Licensee::Old(code) => writeln!(f, "Licensee:         ${:02X}", code)?,
+            Licensee::New(code) => writeln!(f, "Licensee:         {}", code)?,
+        }
+        writeln!(f, "Version:          {}", self.version)?;
+        writeln!(
+            f,
+            "Header checksum:  {}",
+            checksum(&self.header_checksum, 2)
+        )?;
+        write!(
+            f,
+            "Global checksum:  {}",
+            checksum(&self.global_checksum, 4)
+        )
+    }
+}
+
+/// Checksum over 0x0134-0x014C, which the boot ROM refuses to start
+/// without.
+pub fn header_checksum(rom: &[u8]) -> u8 {
+    rom[0x0134..0x014D]
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_sub(*byte).wrapping_sub(1))
+}
+
+/// Sum of every byte in the ROM except the checksum itself. Nothing
+/// actually checks it, so plenty of homebrew gets it wrong.
+pub fn global_checksum(rom: &[u8]) -> u16 {
+    rom.iter()
+        .enumerate()
+        .filter(|(index, _)| *index != 0x014E && *index != 0x014F)
+        .fold(0u16, |sum, (_, byte)| sum.wrapping_add(*byte as u16))
+}
+
+// Header strings are ASCII padded with zeroes
+fn text(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .take_while(|byte| **byte != 0)
+        .map(|byte| match *byte as char {
+            c if c.is_ascii_graphic() || c == ' ' => c,
+            _ => '?',
+        })
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cartridge::header::{CartridgeType, Mbc};
+
+    #[test]
+    fn test_cartridge_type_names() {
+        assert_eq!(CartridgeType::from_code(0x00).to_string(), "ROM ONLY");
+        assert_eq!(
+            CartridgeType::from_code(0x09).to_string(),
+            "ROM+RAM+BATTERY"
+        );
+        assert_eq!(
+            CartridgeType::from_code(0x03).to_string(),
+            "MBC1+RAM+BATTERY"
+        );
+        assert_eq!(
+            CartridgeType::from_code(0x10).to_string(),
+            "MBC3+TIMER+RAM+BATTERY"
+        );
+        assert_eq!(
+            CartridgeType::from_code(0x1E).to_string(),
+            "MBC5+RUMBLE+RAM+BATTERY"
+        );
+        assert_eq!(CartridgeType::from_code(0x04).mbc, Mbc::Unknown);
+    }
+}
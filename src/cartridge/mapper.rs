@@ -0,0 +1,117 @@
+use crate::cartridge::header::{CartridgeType, Mbc};
+use crate::cartridge::mbc1::Mbc1;
+use crate::cartridge::mbc2::Mbc2;
+use crate::cartridge::mbc3::Mbc3;
+use crate::cartridge::mbc5::Mbc5;
+use crate::cartridge::rtc::Rtc;
+
+/// Whatever sits between the CPU and the ROM and RAM chips, turning
+/// addresses into offsets into the whole image.
+#[derive(Debug)]
+pub enum Mapper {
+    // ROM only, 32 KiB mapped straight through
+    None,
+    Mbc1(Mbc1),
+    Mbc2(Mbc2),
+    Mbc3(Mbc3),
+    Mbc5(Mbc5),
+}
+
+impl Mapper {
+    pub fn new(cartridge_type: CartridgeType) -> Option<Mapper> {
+        match cartridge_type.mbc {
+            Mbc::None => Some(Mapper::None),
+            Mbc::Mbc1 => Some(Mapper::Mbc1(Mbc1::new())),
+            Mbc::Mbc2 => Some(Mapper::Mbc2(Mbc2::new())),
+            Mbc::Mbc3 => Some(Mapper::Mbc3(Mbc3::new(cartridge_type.timer))),
+            Mbc::Mbc5 => Some(Mapper::Mbc5(Mbc5::new(cartridge_type.rumble))),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn rom_address(&self, address: u16) -> usize {
+        match self {
+            Mapper::None => address as usize,
+            Mapper::Mbc1(mbc) => mbc.rom_address(address),
+            Mapper::Mbc2(mbc) => mbc.rom_address(address),
+            Mapper::Mbc3(mbc) => mbc.rom_address(address),
+            Mapper::Mbc5(mbc) => mbc.rom_address(address),
+        }
+    }
+
+    pub(crate) fn ram_enabled(&self) -> bool {
+        match self {
+            Mapper::None => true,
+            Mapper::Mbc1(mbc) => mbc.ram_enabled(),
+            Mapper::Mbc2(mbc) => mbc.ram_enabled(),
+            Mapper::Mbc3(mbc) => mbc.ram_enabled(),
+            Mapper::Mbc5(mbc) => mbc.ram_enabled(),
+        }
+    }
+
+    /// None when RAM is disabled.
+    pub(crate) fn ram_address(&self, address: u16) -> Option<usize> {
+        match self {
+            Mapper::None => Some(address as usize - 0xA000),
+            Mapper::Mbc1(mbc) => mbc.ram_address(address),
+            Mapper::Mbc2(mbc) => mbc.ram_address(address),
+            Mapper::Mbc3(mbc) => mbc.ram_address(address),
+            Mapper::Mbc5(mbc) => mbc.ram_address(address),
+        }
+    }
+
+    // Bits of each RAM byte that aren't wired up, and read as 1
+    pub(crate) fn unused_ram_bits(&self) -> u8 {
+        match self {
+            Mapper::Mbc2(_) => 0xF0,
+            _ => 0x00,
+        }
+    }
+
+    // Registers mapped over 0xA000-0xBFFF in place of RAM
+    pub(crate) fn read_register(&self) -> Option<u8> {
+        match self {
+            Mapper::Mbc3(mbc) => mbc.read_rtc(),
+            _ => None,
+        }
+    }
+
+    // Returns whether a register took the write instead of RAM
+    pub(crate) fn write_register(&mut self, data: u8) -> bool {
+        match self {
+            Mapper::Mbc3(mbc) => mbc.write_rtc(data),
+            _ => false,
+        }
+    }
+
+    // Writes to 0x0000-0x7FFF
+    pub(crate) fn write(&mut self, address: u16, data: u8) {
+        match self {
+            Mapper::None => {}
+            Mapper::Mbc1(mbc) => mbc.write(address, data),
+            Mapper::Mbc2(mbc) => mbc.write(address, data),
+            Mapper::Mbc3(mbc) => mbc.write(address, data),
+            Mapper::Mbc5(mbc) => mbc.write(address, data),
+        }
+    }
+
+    pub(crate) fn tick(&mut self, double_speed: bool) {
+        if let Mapper::Mbc3(mbc) = self {
+            mbc.tick(double_speed);
+        }
+    }
+
+    pub fn rumble(&self) -> bool {
+        match self {
+            Mapper::Mbc5(mbc) => mbc.rumble(),
+            _ => false,
+        }
+    }
+
+    pub fn rtc_mut(&mut self) -> Option<&mut Rtc> {
+        match self {
+            Mapper::Mbc3(mbc) => mbc.rtc.as_mut(),
+            _ => None,
+        }
+    }
+}
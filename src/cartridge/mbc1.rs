@@ -0,0 +1,84 @@
+/// MBC1, good for up to 2 MiB of ROM and 32 KiB of RAM.
+#[derive(Debug, Default)]
+pub struct Mbc1 {
+    ram_enabled: bool,
+    // 5 bits, selects the 0x4000-0x7FFF bank. Never 0.
+    bank1: u8,
+    // 2 bits, either the top of the ROM bank or the RAM bank
+    bank2: u8,
+    // In mode 1 bank2 also applies to 0x0000-0x3FFF and to RAM
+    advanced_mode: bool,
+}
+
+impl Mbc1 {
+    pub fn new() -> Mbc1 {
+        Mbc1 {
+            bank1: 1,
+            ..Mbc1::default()
+        }
+    }
+
+    pub(crate) fn ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    pub(crate) fn rom_address(&self, address: u16) -> usize {
+        let bank = match address {
+            0x0000..=0x3FFF if self.advanced_mode => self.bank2 << 5,
+            0x0000..=0x3FFF => 0,
+            _ => self.bank2 << 5 | self.bank1,
+        };
+        bank as usize * 0x4000 + (address as usize & 0x3FFF)
+    }
+
+    pub(crate) fn ram_address(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+        let bank = if self.advanced_mode { self.bank2 } else { 0 };
+        Some(bank as usize * 0x2000 + (address as usize & 0x1FFF))
+    }
+
+    pub(crate) fn write(&mut self, address: u16, data: u8) {
+        match address {
+            // Any value with 0xA in the low nibble enables RAM
+            0x0000..=0x1FFF => self.ram_enabled = data & 0x0F == 0x0A,
+            // Bank 0 can't be picked here, it turns into 1. The check is
+            // against all five bits, so 0x20, 0x40 and 0x60 are out of
+            // reach in mode 0.
+            0x2000..=0x3FFF => self.bank1 = (data & 0x1F).max(1),
+            0x4000..=0x5FFF => self.bank2 = data & 0x03,
+            0x6000..=0x7FFF => self.advanced_mode = data & 0x01 != 0,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cartridge::mbc1::Mbc1;
+
+    #[test]
+    fn test_bank_zero_is_one() {
+        let mut mbc = Mbc1::new();
+        mbc.write(0x2000, 0x00);
+        assert_eq!(mbc.rom_address(0x4000), 0x4000);
+        mbc.write(0x2000, 0x20);
+        assert_eq!(mbc.rom_address(0x4000), 0x4000);
+        mbc.write(0x4000, 0x01);
+        assert_eq!(mbc.rom_address(0x4000), 0x21 * 0x4000);
+    }
+
+    #[test]
+    fn test_banking_mode() {
+        let mut mbc = Mbc1::new();
+        mbc.write(0x0000, 0x0A);
+        mbc.write(0x4000, 0x02);
+        assert_eq!(mbc.rom_address(0x0000), 0x0000);
+        assert_eq!(mbc.ram_address(0xA000), Some(0x0000));
+
+        mbc.write(0x6000, 0x01);
+        assert_eq!(mbc.rom_address(0x0000), 0x40 * 0x4000);
+        assert_eq!(mbc.ram_address(0xA000), Some(0x4000));
+    }
+}
@@ -0,0 +1,97 @@
+/// 512 half-bytes of RAM, built into the MBC2 itself.
+pub const RAM_SIZE: usize = 0x200;
+
+/// MBC2, up to 256 KiB of ROM.
+#[derive(Debug, Default)]
+pub struct Mbc2 {
+    ram_enabled: bool,
+    // 4 bits, never 0
+    rom_bank: u8,
+}
+
+impl Mbc2 {
+    pub fn new() -> Mbc2 {
+        Mbc2 {
+            rom_bank: 1,
+            ..Mbc2::default()
+        }
+    }
+
+    pub(crate) fn ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    pub(crate) fn rom_address(&self, address: u16) -> usize {
+        let bank = match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank,
+        };
+        bank as usize * 0x4000 + (address as usize & 0x3FFF)
+    }
+
+    // The 512 bytes repeat all the way through 0xA000-0xBFFF
+    pub(crate) fn ram_address(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+        Some(address as usize & 0x01FF)
+    }
+
+    pub(crate) fn write(&mut self, address: u16, data: u8) {
+        // There's a single register in 0x0000-0x3FFF, and address bit 8
+        // picks which half of it the write is for
+        match address {
+            0x0000..=0x3FFF if address & 0x0100 == 0 => self.ram_enabled = data & 0x0F == 0x0A,
+            0x0000..=0x3FFF => self.rom_bank = (data & 0x0F).max(1),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cartridge::mbc2::Mbc2;
+
+    #[test]
+    fn test_register_select() {
+        let mut mbc = Mbc2::new();
+        // Bit 8 clear, so this is the RAM enable and not a bank
+        mbc.write(0x0000, 0x0A);
+        assert_eq!(mbc.rom_address(0x4000), 0x4000);
+        assert_eq!(mbc.ram_address(0xA000), Some(0x0000));
+
+        mbc.write(0x0100, 0x05);
+        assert_eq!(mbc.rom_address(0x4000), 0x5 * 0x4000);
+        assert_eq!(mbc.ram_address(0xA000), Some(0x0000));
+
+        // Anywhere up to 0x3FFF works, as long as bit 8 matches
+        mbc.write(0x3EFF, 0x00);
+        assert_eq!(mbc.ram_address(0xA000), None);
+        mbc.write(0x3FFF, 0x03);
+        assert_eq!(mbc.rom_address(0x4000), 0x3 * 0x4000);
+    }
+
+    #[test]
+    fn test_rom_bank() {
+        let mut mbc = Mbc2::new();
+        mbc.write(0x2100, 0x00);
+        assert_eq!(mbc.rom_address(0x4000), 0x4000);
+        mbc.write(0x2100, 0xFF);
+        assert_eq!(mbc.rom_address(0x7FFF), 0xF * 0x4000 + 0x3FFF);
+        assert_eq!(mbc.rom_address(0x0000), 0x0000);
+
+        // Writes above 0x3FFF do nothing
+        mbc.write(0x4100, 0x02);
+        mbc.write(0x6100, 0x02);
+        assert_eq!(mbc.rom_address(0x4000), 0xF * 0x4000);
+    }
+
+    #[test]
+    fn test_ram_echo() {
+        let mut mbc = Mbc2::new();
+        mbc.write(0x0000, 0x0A);
+        assert_eq!(mbc.ram_address(0xA1FF), Some(0x01FF));
+        assert_eq!(mbc.ram_address(0xA200), Some(0x0000));
+        assert_eq!(mbc.ram_address(0xBFFF), Some(0x01FF));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::cartridge::rtc::Rtc;
+
+/// MBC3, up to 2 MiB of ROM and 32 KiB of RAM, sometimes with a clock.
+#[derive(Debug)]
+pub struct Mbc3 {
+    // Covers the clock registers too
+    ram_enabled: bool,
+    // 7 bits, never 0
+    rom_bank: u8,
+    // 0x00-0x03 picks a RAM bank, 0x08-0x0C a clock register
+    ram_bank: u8,
+    // The clock latches on a 0x00 write followed by 0x01
+    latch_armed: bool,
+    pub(crate) rtc: Option<Rtc>,
+}
+
+impl Mbc3 {
+    pub fn new(timer: bool) -> Mbc3 {
+        Mbc3 {
+            ram_enabled: false,
+            rom_bank: 1,
+            ram_bank: 0,
+            latch_armed: false,
+            rtc: if timer { Some(Rtc::default()) } else { None },
+        }
+    }
+
+    pub(crate) fn ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    pub(crate) fn rom_address(&self, address: u16) -> usize {
+        let bank = match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank,
+        };
+        bank as usize * 0x4000 + (address as usize & 0x3FFF)
+    }
+
+    pub(crate) fn ram_address(&self, address: u16) -> Option<usize> {
+        match self.ram_bank {
+            0x00..=0x03 if self.ram_enabled => {
+                Some(self.ram_bank as usize * 0x2000 + (address as usize & 0x1FFF))
+            }
+            _ => None,
+        }
+    }
+
+    // Some(..) when a clock register is mapped in place of RAM
+    pub(crate) fn read_rtc(&self) -> Option<u8> {
+        match (&self.rtc, self.ram_bank) {
+            (Some(rtc), 0x08..=0x0C) if self.ram_enabled => Some(rtc.read(self.ram_bank)),
+            (_, 0x08..=0x0C) => Some(0xFF),
+            _ => None,
+        }
+    }
+
+    // Returns whether the write went to a clock register
+    pub(crate) fn write_rtc(&mut self, data: u8) -> bool {
+        match (&mut self.rtc, self.ram_bank) {
+            (Some(rtc), 0x08..=0x0C) if self.ram_enabled => {
+                rtc.write(self.ram_bank, data);
+                true
+            }
+            (_, 0x08..=0x0C) => true,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn write(&mut self, address: u16, data: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = data & 0x0F == 0x0A,
+            0x2000..=0x3FFF => self.rom_bank = (data & 0x7F).max(1),
+            0x4000..=0x5FFF => self.ram_bank = data & 0x0F,
+            0x6000..=0x7FFF => {
+                if self.latch_armed && data == 0x01 {
+                    if let Some(rtc) = &mut self.rtc {
+                        rtc.latch();
+                    }
+                }
+                self.latch_armed = data == 0x00;
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn tick(&mut self, double_speed: bool) {
+        if let Some(rtc) = &mut self.rtc {
+            rtc.tick(double_speed);
+        }
+    }
+}
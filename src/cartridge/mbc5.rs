@@ -0,0 +1,129 @@
+/// MBC5, up to 8 MiB of ROM and 128 KiB of RAM.
+#[derive(Debug, Default)]
+pub struct Mbc5 {
+    ram_enabled: bool,
+    // 9 bits, and unlike the older MBCs bank 0 can be mapped here
+    rom_bank: u16,
+    ram_bank: u8,
+    // Rumble carts wire bit 3 of the RAM bank to the motor instead
+    has_rumble: bool,
+    rumble: bool,
+}
+
+impl Mbc5 {
+    pub fn new(has_rumble: bool) -> Mbc5 {
+        Mbc5 {
+            rom_bank: 1,
+            has_rumble,
+            ..Mbc5::default()
+        }
+    }
+
+    /// Whether the rumble motor is running.
+    pub fn rumble(&self) -> bool {
+        self.rumble
+    }
+
+    pub(crate) fn ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    pub(crate) fn rom_address(&self, address: u16) -> usize {
+        let bank = match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank,
+        };
+        bank as usize * 0x4000 + (address as usize & 0x3FFF)
+    }
+
+    pub(crate) fn ram_address(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+        Some(self.ram_bank as usize * 0x2000 + (address as usize & 0x1FFF))
+    }
+
+    pub(crate) fn write(&mut self, address: u16, data: u8) {
+        match address {
+            // Only 0x0A itself enables RAM here
+            0x0000..=0x1FFF => self.ram_enabled = data == 0x0A,
+            0x2000..=0x2FFF => self.rom_bank = self.rom_bank & 0x100 | data as u16,
+            0x3000..=0x3FFF => self.rom_bank = self.rom_bank & 0xFF | (data as u16 & 0x01) << 8,
+            0x4000..=0x5FFF if self.has_rumble => {
+                self.rumble = data & 0x08 != 0;
+                self.ram_bank = data & 0x07;
+            }
+            0x4000..=0x5FFF => self.ram_bank = data & 0x0F,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cartridge::mbc5::Mbc5;
+
+    #[test]
+    fn test_rom_bank_zero() {
+        let mut mbc = Mbc5::new(false);
+        assert_eq!(mbc.rom_address(0x4000), 0x4000);
+        mbc.write(0x2000, 0x00);
+        assert_eq!(mbc.rom_address(0x4000), 0x0000);
+        assert_eq!(mbc.rom_address(0x7FFF), 0x3FFF);
+    }
+
+    #[test]
+    fn test_ninth_bit() {
+        let mut mbc = Mbc5::new(false);
+        mbc.write(0x2000, 0xFF);
+        mbc.write(0x3000, 0x01);
+        assert_eq!(mbc.rom_address(0x4000), 0x1FF * 0x4000);
+
+        // Either half can change without touching the other
+        mbc.write(0x2FFF, 0x02);
+        assert_eq!(mbc.rom_address(0x4000), 0x102 * 0x4000);
+        mbc.write(0x3FFF, 0xFE);
+        assert_eq!(mbc.rom_address(0x4000), 0x002 * 0x4000);
+
+        // 0x0000-0x3FFF stays on bank 0
+        assert_eq!(mbc.rom_address(0x0000), 0x0000);
+    }
+
+    #[test]
+    fn test_ram_enable() {
+        let mut mbc = Mbc5::new(false);
+        assert_eq!(mbc.ram_address(0xA000), None);
+        mbc.write(0x0000, 0x0A);
+        assert_eq!(mbc.ram_address(0xA000), Some(0x0000));
+        mbc.write(0x0000, 0x1A);
+        assert_eq!(mbc.ram_address(0xA000), None);
+    }
+
+    #[test]
+    fn test_ram_banks() {
+        let mut mbc = Mbc5::new(false);
+        mbc.write(0x0000, 0x0A);
+        mbc.write(0x4000, 0x0F);
+        assert_eq!(mbc.ram_address(0xBFFF), Some(0xF * 0x2000 + 0x1FFF));
+        mbc.write(0x4000, 0xF3);
+        assert_eq!(mbc.ram_address(0xA000), Some(0x3 * 0x2000));
+    }
+
+    #[test]
+    fn test_rumble() {
+        let mut mbc = Mbc5::new(true);
+        mbc.write(0x0000, 0x0A);
+        mbc.write(0x4000, 0x09);
+        assert!(mbc.rumble());
+        assert_eq!(mbc.ram_address(0xA000), Some(0x2000));
+        mbc.write(0x4000, 0x01);
+        assert!(!mbc.rumble());
+
+        // Without a motor, bit 3 is just part of the bank
+        let mut mbc = Mbc5::new(false);
+        mbc.write(0x0000, 0x0A);
+        mbc.write(0x4000, 0x09);
+        assert!(!mbc.rumble());
+        assert_eq!(mbc.ram_address(0xA000), Some(0x9 * 0x2000));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::cartridge::error::CartridgeError;
+use crate::cartridge::header::{Header, Mbc};
+use crate::cartridge::mapper::Mapper;
+use crate::cartridge::rtc::Rtc;
+
+pub mod error;
+pub mod header;
+pub mod mapper;
+pub mod mbc1;
+pub mod mbc2;
+pub mod mbc3;
+pub mod mbc5;
+pub mod rtc;
+pub mod save;
+
+/// A ROM image along with whatever RAM the cartridge carries. The CPU only
+/// ever sees it through 0x0000-0x7FFF and 0xA000-0xBFFF.
+#[derive(Debug)]
+pub struct Cartridge {
+    header: Header,
+    mapper: Mapper,
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    // RAM has changed since it was last saved
+    dirty: bool,
+    // The game turned RAM off after changing it, so it's probably done
+    // saving
+    flush_requested: bool,
+}
+
+impl Cartridge {
+    /// Checks the image against its own header before accepting it.
+    pub fn new(rom: Vec<u8>) -> Result<Cartridge, CartridgeError> {
+        let header = Header::parse(&rom)?;
+
+        if !header.header_checksum.is_valid() {
+            return Err(CartridgeError::BadHeaderChecksum {
+                expected: header.header_checksum.expected as u8,
+                computed: header.header_checksum.computed as u8,
+            });
+        }
+        let expected = header.rom_size().ok_or(CartridgeError::UnknownRomSize {
+            code: header.rom_size_code,
+        })?;
+        if rom.len() < expected {
+            return Err(CartridgeError::Truncated {
+                expected,
+                actual: rom.len(),
+            });
+        }
+        if rom.len() > expected {
+            return Err(CartridgeError::Oversized {
+                expected,
+                actual: rom.len(),
+            });
+        }
+
+        let cartridge_type = header.cartridge_type;
+        let mapper = Mapper::new(cartridge_type)
+            .ok_or(CartridgeError::UnsupportedCartridgeType(cartridge_type))?;
+        let ram = match cartridge_type.mbc {
+            Mbc::Mbc2 => vec![0; mbc2::RAM_SIZE],
+            _ if cartridge_type.ram => vec![0; header.ram_size().unwrap_or(0)],
+            _ => vec![],
+        };
+
+        Ok(Cartridge {
+            header,
+            mapper,
+            rom,
+            ram,
+            dirty: false,
+            flush_requested: false,
+        })
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Whether there's a battery keeping RAM, and the clock, alive.
+    pub fn has_battery(&self) -> bool {
+        self.header.cartridge_type.battery
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn flush_requested(&self) -> bool {
+        self.flush_requested
+    }
+
+    /// Whether the rumble motor is running.
+    pub fn rumble(&self) -> bool {
+        self.mapper.rumble()
+    }
+
+    pub fn rtc_mut(&mut self) -> Option<&mut Rtc> {
+        self.mapper.rtc_mut()
+    }
+
+    /// Battery RAM as other emulators store it, with the clock appended
+    /// when there is one. Counts as saved.
+    pub fn save_data(&mut self) -> Vec<u8> {
+        self.dirty = false;
+        self.flush_requested = false;
+        let mut data = self.ram.clone();
+        if let Some(rtc) = self.mapper.rtc_mut() {
+            data.extend_from_slice(&rtc.save());
+        }
+        data
+    }
+
+    /// The reverse of `save_data`. Saves without a clock, or from a
+    /// cartridge with less RAM, load as much as they have.
+    pub fn load_save_data(&mut self, data: &[u8]) {
+        let len = data.len().min(self.ram.len());
+        self.ram[..len].copy_from_slice(&data[..len]);
+        if let Some(rtc) = self.mapper.rtc_mut() {
+            if let Ok(clock) = data[len..].try_into() {
+                rtc.load(clock);
+            }
+        }
+    }
+
+    pub(crate) fn tick(&mut self, double_speed: bool) {
+        self.mapper.tick(double_speed);
+    }
+
+    pub(crate) fn read(&self, address: u16) -> u8 {
+        match address {
+            // Bank numbers past the end of the ROM wrap around
+            0x0000..=0x7FFF => self.rom[self.mapper.rom_address(address) % self.rom.len()],
+            // Reads from RAM that is disabled or isn't there float high
+            0xA000..=0xBFFF => match self.mapper.read_register() {
+                Some(data) => data,
+                None => match self.ram_offset(address) {
+                    Some(offset) => self.ram[offset] | self.mapper.unused_ram_bits(),
+                    None => 0xFF,
+                },
+            },
+            _ => 0xFF,
+        }
+    }
+
+    pub(crate) fn write(&mut self, address: u16, data: u8) {
+        match address {
+            0x0000..=0x7FFF => {
+                let ram_enabled = self.mapper.ram_enabled();
+                self.mapper.write(address, data);
+                if self.dirty && ram_enabled && !self.mapper.ram_enabled() {
+                    self.flush_requested = true;
+                }
+            }
+            0xA000..=0xBFFF => {
+                if self.mapper.write_register(data) {
+                    self.dirty = true;
+                    return;
+                }
+                if let Some(offset) = self.ram_offset(address) {
+                    self.ram[offset] = data & !self.mapper.unused_ram_bits();
+                    self.dirty = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        if self.ram.is_empty() {
+            return None;
+        }
+        self.mapper
+            .ram_address(address)
+            .map(|offset| offset % self.ram.len())
+    }
+}
@@ -0,0 +1,267 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// The RTC crystal runs at 32768 Hz no matter the CPU speed, so count in
+// double speed M-cycles, of which there are 2^21 a second
+const TICKS_PER_SECOND: u32 = 0x200000;
+
+// Size of the RTC block other emulators append to the save RAM
+pub const RTC_SAVE_LEN: usize = 48;
+
+/// The MBC3 real-time clock.
+#[derive(Debug, Default)]
+pub struct Rtc {
+    seconds: u8,
+    minutes: u8,
+    hours: u8,
+    // 9 bits
+    days: u16,
+    halted: bool,
+    // Set when the day counter overflows, and stays set until cleared
+    day_carry: bool,
+    // What the CPU sees, copied from the live registers on latch
+    latched: [u8; 5],
+    ticks: u32,
+    // Unix time the clock was last brought up to date, when it follows the
+    // host instead of emulated time
+    host_sync: Option<u64>,
+}
+
+impl Rtc {
+    /// Follow the host's wall clock instead of counting emulated cycles.
+    pub fn set_host_clock(&mut self, enabled: bool) {
+        self.host_sync = if enabled { Some(now()) } else { None };
+    }
+
+    pub(crate) fn tick(&mut self, double_speed: bool) {
+        if self.halted || self.host_sync.is_some() {
+            return;
+        }
+        self.ticks += if double_speed { 1 } else { 2 };
+        if self.ticks >= TICKS_PER_SECOND {
+            self.ticks -= TICKS_PER_SECOND;
+            self.tick_second();
+        }
+    }
+
+    pub(crate) fn latch(&mut self) {
+        self.sync();
+        self.latched = self.registers();
+    }
+
+    // 0x08-0x0C
+    pub(crate) fn read(&self, register: u8) -> u8 {
+        self.latched[(register - 0x08) as usize]
+    }
+
+    pub(crate) fn write(&mut self, register: u8, data: u8) {
+        self.sync();
+        match register {
+            // Writing the seconds also restarts the current second
+            0x08 => {
+                self.seconds = data & 0x3F;
+                self.ticks = 0;
+            }
+            0x09 => self.minutes = data & 0x3F,
+            0x0A => self.hours = data & 0x1F,
+            0x0B => self.days = self.days & 0x100 | data as u16,
+            0x0C => {
+                self.days = self.days & 0xFF | (data as u16 & 0x01) << 8;
+                self.halted = data & 0x40 != 0;
+                self.day_carry = data & 0x80 != 0;
+            }
+            _ => {}
+        }
+        // Writes show up without a latch
+        self.latched[(register - 0x08) as usize] = self.registers()[(register - 0x08) as usize];
+    }
+
+    /// The layout used by VBA and BGB: the live and latched registers as
+    /// little endian u32s, then a u64 unix timestamp.
+    pub fn save(&mut self) -> [u8; RTC_SAVE_LEN] {
+        self.sync();
+        let mut data = [0; RTC_SAVE_LEN];
+        for (index, register) in self
+            .registers()
+            .iter()
+            .chain(self.latched.iter())
+            .enumerate()
+        {
+            data[index * 4] = *register;
+        }
+        data[40..].copy_from_slice(&now().to_le_bytes());
+        data
+    }
+
+    pub fn load(&mut self, data: &[u8; RTC_SAVE_LEN]) {
+        let register = |index: usize| data[index * 4];
+        self.seconds = register(0) & 0x3F;
+        self.minutes = register(1) & 0x3F;
+        self.hours = register(2) & 0x1F;
+        self.days = register(3) as u16 | (register(4) as u16 & 0x01) << 8;
+        self.halted = register(4) & 0x40 != 0;
+        self.day_carry = register(4) & 0x80 != 0;
+        for index in 0..5 {
+            self.latched[index] = register(index + 5);
+        }
+        self.ticks = 0;
+
+        // On the host clock, the time the emulator was closed counts too
+        if self.host_sync.is_some() {
+            let mut timestamp = [0; 8];
+            timestamp.copy_from_slice(&data[40..]);
+            self.host_sync = Some(u64::from_le_bytes(timestamp));
+            self.sync();
+        }
+    }
+
+    fn registers(&self) -> [u8; 5] {
+        let mut day_high = (self.days >> 8) as u8;
+        if self.halted {
+            day_high |= 0x40;
+        }
+        if self.day_carry {
+            day_high |= 0x80;
+        }
+        [
+            self.seconds,
+            self.minutes,
+            self.hours,
+            self.days as u8,
+            day_high,
+        ]
+    }
+
+    // Catch up with the host clock
+    fn sync(&mut self) {
+        if let Some(last) = self.host_sync {
+            let now = now();
+            if !self.halted {
+                self.advance(now.saturating_sub(last));
+            }
+            self.host_sync = Some(now);
+        }
+    }
+
+    fn advance(&mut self, mut seconds: u64) {
+        // Out of range values count up until they wrap, so take those one at
+        // a time, then do the rest in bulk
+        while seconds > 0 && (self.seconds >= 60 || self.minutes >= 60 || self.hours >= 24) {
+            self.tick_second();
+            seconds -= 1;
+        }
+        let total = ((self.days as u64 * 24 + self.hours as u64) * 60 + self.minutes as u64) * 60
+            + self.seconds as u64
+            + seconds;
+        self.seconds = (total % 60) as u8;
+        self.minutes = (total / 60 % 60) as u8;
+        self.hours = (total / 3600 % 24) as u8;
+        let days = total / 86400;
+        if days > 0x1FF {
+            self.day_carry = true;
+        }
+        self.days = (days & 0x1FF) as u16;
+    }
+
+    fn tick_second(&mut self) {
+        self.seconds = (self.seconds + 1) & 0x3F;
+        if self.seconds != 60 {
+            return;
+        }
+        self.seconds = 0;
+        self.minutes = (self.minutes + 1) & 0x3F;
+        if self.minutes != 60 {
+            return;
+        }
+        self.minutes = 0;
+        self.hours = (self.hours + 1) & 0x1F;
+        if self.hours != 24 {
+            return;
+        }
+        self.hours = 0;
+        self.days += 1;
+        if self.days > 0x1FF {
+            self.days = 0;
+            self.day_carry = true;
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cartridge::rtc::{Rtc, TICKS_PER_SECOND};
+
+    fn run_seconds(rtc: &mut Rtc, seconds: u32) {
+        for _ in 0..seconds * TICKS_PER_SECOND / 2 {
+            rtc.tick(false);
+        }
+    }
+
+    #[test]
+    fn test_rollover() {
+        let mut rtc = Rtc::default();
+        rtc.write(0x08, 59);
+        rtc.write(0x09, 59);
+        rtc.write(0x0A, 23);
+        rtc.write(0x0B, 0xFF);
+        rtc.write(0x0C, 0x01);
+        run_seconds(&mut rtc, 1);
+        rtc.latch();
+        assert_eq!(
+            [0x08, 0x09, 0x0A, 0x0B, 0x0C].map(|register| rtc.read(register)),
+            [0, 0, 0, 0, 0x80]
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_seconds() {
+        let mut rtc = Rtc::default();
+        rtc.write(0x08, 63);
+        run_seconds(&mut rtc, 1);
+        rtc.latch();
+        assert_eq!(rtc.read(0x08), 0);
+        assert_eq!(rtc.read(0x09), 0);
+
+        rtc.write(0x08, 62);
+        rtc.advance(3);
+        assert_eq!(rtc.seconds, 1);
+        assert_eq!(rtc.minutes, 0);
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut rtc = Rtc::default();
+        rtc.advance(511 * 86400 + 3661);
+        assert_eq!(
+            (rtc.days, rtc.hours, rtc.minutes, rtc.seconds),
+            (511, 1, 1, 1)
+        );
+        assert!(!rtc.day_carry);
+        rtc.advance(86400);
+        assert_eq!(rtc.days, 0);
+        assert!(rtc.day_carry);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut rtc = Rtc::default();
+        rtc.write(0x08, 12);
+        rtc.write(0x0B, 0x34);
+        rtc.write(0x0C, 0x41);
+        let data = rtc.save();
+        assert_eq!(data[0], 12);
+        assert_eq!(data[12], 0x34);
+        assert_eq!(data[16], 0x41);
+
+        let mut loaded = Rtc::default();
+        loaded.load(&data);
+        assert_eq!(loaded.registers(), rtc.registers());
+        assert_eq!(loaded.latched, rtc.latched);
+    }
+}
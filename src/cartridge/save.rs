@@ -0,0 +1,70 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cartridge::Cartridge;
+
+// About five seconds of emulated time
+const FLUSH_INTERVAL: u64 = 5 * 0x100000;
+
+/// Keeps battery RAM in a .sav file, in the raw format other emulators
+/// use. Does nothing for cartridges without a battery.
+#[derive(Debug)]
+pub struct SaveFile {
+    path: PathBuf,
+    cycles: u64,
+}
+
+impl SaveFile {
+    pub fn new(path: PathBuf) -> SaveFile {
+        SaveFile { path, cycles: 0 }
+    }
+
+    /// `game.gb` saves to `game.sav`, next to the ROM unless a directory is
+    /// given.
+    pub fn for_rom(rom_path: &Path, save_dir: Option<&Path>) -> SaveFile {
+        let path = rom_path.with_extension("sav");
+        match (save_dir, path.file_name()) {
+            (Some(dir), Some(name)) => SaveFile::new(dir.join(name)),
+            _ => SaveFile::new(path),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A missing file is just a game that hasn't saved yet.
+    pub fn load(&self, cartridge: &mut Cartridge) -> io::Result<()> {
+        if !cartridge.has_battery() {
+            return Ok(());
+        }
+        match fs::read(&self.path) {
+            Ok(data) => {
+                cartridge.load_save_data(&data);
+                Ok(())
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn flush(&mut self, cartridge: &mut Cartridge) -> io::Result<()> {
+        self.cycles = 0;
+        if !cartridge.has_battery() {
+            return Ok(());
+        }
+        fs::write(&self.path, cartridge.save_data())
+    }
+
+    /// Flushes once the game turns RAM off after writing to it, or every so
+    /// often if it never does.
+    pub fn tick(&mut self, cartridge: &mut Cartridge, cycles: u64) -> io::Result<()> {
+        self.cycles += cycles;
+        if cartridge.flush_requested() || (cartridge.is_dirty() && self.cycles >= FLUSH_INTERVAL) {
+            self.flush(cartridge)
+        } else {
+            Ok(())
+        }
+    }
+}
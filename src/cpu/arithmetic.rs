@@ -44,6 +44,26 @@ impl CPU {
         a + b
     }
 
+    /// SP + s8, shared by ADD SP, s8 and LD HL, SP+s8. Unlike other 16 bit
+    /// additions, H and C come from adding the offset (as an unsigned byte)
+    /// to the low byte of SP, and Z is always cleared.
+    pub fn add_sp_signed(&mut self, sp: Value, b: i8) -> Value {
+        let low = sp.low_byte();
+        if Self::check_half_carry_add_signed(low, b) {
+            self.registers.f.set(H);
+        } else {
+            self.registers.f.unset(H);
+        }
+        if Self::check_carry_add(low, Value::EightBit(b as u8)) {
+            self.registers.f.set(C);
+        } else {
+            self.registers.f.unset(C);
+        }
+        self.registers.f.unset(Z);
+        self.registers.f.unset(N);
+        sp + b
+    }
+
     pub fn sub(&mut self, a: Value, b: Value) -> Value {
         if Self::check_half_carry_sub(a, b) {
             self.registers.f.set(H);
@@ -60,6 +80,71 @@ impl CPU {
         a - b
     }
 
+    /// 8 bit ADC. H and C come from adding a, b and the carry in one go, so
+    /// a carry out of b plus the carry isn't lost.
+    pub fn adc(&mut self, a: Value, b: Value) -> Value {
+        let carry = u8::from(self.registers.f.is_set(C));
+        let (a, b) = (a.extract() as u8, b.extract() as u8);
+        if (a & 0x0F) + (b & 0x0F) + carry > 0x0F {
+            self.registers.f.set(H);
+        } else {
+            self.registers.f.unset(H);
+        }
+        if u16::from(a) + u16::from(b) + u16::from(carry) > 0xFF {
+            self.registers.f.set(C);
+        } else {
+            self.registers.f.unset(C);
+        }
+        let result = Value::EightBit(a.wrapping_add(b).wrapping_add(carry));
+        self.check_zero_flag(result);
+        self.registers.f.unset(N);
+        result
+    }
+
+    /// 8 bit SBC. Like ADC, the borrows come from a - b - carry as a whole.
+    pub fn sbc(&mut self, a: Value, b: Value) -> Value {
+        let carry = u8::from(self.registers.f.is_set(C));
+        let (a, b) = (a.extract() as u8, b.extract() as u8);
+        if (a & 0x0F) < (b & 0x0F) + carry {
+            self.registers.f.set(H);
+        } else {
+            self.registers.f.unset(H);
+        }
+        if u16::from(a) < u16::from(b) + u16::from(carry) {
+            self.registers.f.set(C);
+        } else {
+            self.registers.f.unset(C);
+        }
+        let result = Value::EightBit(a.wrapping_sub(b).wrapping_sub(carry));
+        self.check_zero_flag(result);
+        self.registers.f.set(N);
+        result
+    }
+
+    /// 8 bit INC. Same as adding one, but C is left untouched.
+    pub fn inc(&mut self, a: Value) -> Value {
+        let carry = self.registers.f.is_set(C);
+        let result = self.add(a, Value::EightBit(1));
+        if carry {
+            self.registers.f.set(C);
+        } else {
+            self.registers.f.unset(C);
+        }
+        result
+    }
+
+    /// 8 bit DEC. Same as subtracting one, but C is left untouched.
+    pub fn dec(&mut self, a: Value) -> Value {
+        let carry = self.registers.f.is_set(C);
+        let result = self.sub(a, Value::EightBit(1));
+        if carry {
+            self.registers.f.set(C);
+        } else {
+            self.registers.f.unset(C);
+        }
+        result
+    }
+
     pub fn rol(&mut self, a: Value, use_carry: bool) -> Value {
         let result = match a {
             Value::EightBit(_) => {
@@ -279,14 +364,14 @@ pub fn unsigned_to_signed_16(value: Value) -> i16 {
     match value {
         Value::EightBit(a) => {
             if a & 0x80 != 0 {
-                -((!a).wrapping_add(1) as i8 as i16)
+                ((!a).wrapping_add(1) as i16).wrapping_neg()
             } else {
                 a as i16
             }
         }
         Value::SixteenBit(a) => {
             if a & 0x8000 != 0 {
-                -((!a).wrapping_add(1) as i16)
+                ((!a).wrapping_add(1) as i16).wrapping_neg()
             } else {
                 a as i16
             }
@@ -300,7 +385,7 @@ pub fn unsigned_to_signed_8(value: Value) -> i8 {
     match value {
         Value::EightBit(a) => {
             if a & 0x80 != 0 {
-                -((!a).wrapping_add(1) as i8)
+                ((!a).wrapping_add(1) as i8).wrapping_neg()
             } else {
                 a as i8
             }
@@ -317,8 +402,8 @@ pub fn unsigned_to_signed_8(value: Value) -> i8 {
 
 #[cfg(test)]
 mod tests {
-    use crate::cpu::arithmetic::unsigned_to_signed_16;
-    use crate::cpu::flag::Flag::C;
+    use crate::cpu::arithmetic::{unsigned_to_signed_16, unsigned_to_signed_8};
+    use crate::cpu::flag::Flag::{C, H, Z};
     use crate::cpu::value::Value;
     use crate::cpu::CPU;
 
@@ -331,6 +416,24 @@ mod tests {
         assert_eq!(h, Value::EightBit(0x4D));
     }
 
+    #[test]
+    fn test_add_sp_signed() {
+        let mut cpu = CPU::default();
+        let sp = Value::SixteenBit(0xFFF8);
+        let result = cpu.add_sp_signed(sp, 0x08);
+        assert_eq!(result, Value::SixteenBit(0x0000));
+        assert!(cpu.registers.f.is_set(H));
+        assert!(cpu.registers.f.is_set(C));
+        assert!(!cpu.registers.f.is_set(Z));
+
+        // Negative offsets are added as unsigned bytes when computing flags
+        let sp = Value::SixteenBit(0x0000);
+        let result = cpu.add_sp_signed(sp, -1);
+        assert_eq!(result, Value::SixteenBit(0xFFFF));
+        assert!(!cpu.registers.f.is_set(H));
+        assert!(!cpu.registers.f.is_set(C));
+    }
+
     #[test]
     fn test_check_half_carry_add() {
         let a = Value::EightBit(0x80);
@@ -398,6 +501,10 @@ mod tests {
         assert_eq!(ia as i8, 0b1011_1101u8 as i8);
         assert_eq!(ib, -29457);
         assert_eq!(ib, 0b1000_1100_1110_1111u16 as i16);
+
+        assert_eq!(unsigned_to_signed_16(Value::EightBit(0x80)), -128);
+        assert_eq!(unsigned_to_signed_16(Value::SixteenBit(0x8000)), i16::MIN);
+        assert_eq!(unsigned_to_signed_8(Value::EightBit(0x80)), i8::MIN);
     }
 
     #[test]
@@ -0,0 +1,65 @@
+/// OAM DMA. Writing a high byte to 0xFF46 copies the 160 bytes from there
+/// into OAM, one per M-cycle.
+#[derive(Debug, Default)]
+pub struct Dma {
+    // The high byte of the source, as last written
+    source: u8,
+    // Next byte to copy, while a transfer is running
+    position: Option<u8>,
+}
+
+impl Dma {
+    pub(crate) fn read(&self) -> u8 {
+        self.source
+    }
+
+    // Starting again partway through restarts from the first byte
+    pub(crate) fn start(&mut self, source: u8) {
+        self.source = source;
+        self.position = Some(0);
+    }
+
+    pub(crate) fn active(&self) -> bool {
+        self.position.is_some()
+    }
+
+    /// Runs one M-cycle. Returns the source and OAM addresses of the byte to
+    /// copy, while a transfer is running.
+    pub(crate) fn tick(&mut self) -> Option<(u16, u16)> {
+        let position = self.position?;
+        self.position = if position + 1 < 0xA0 {
+            Some(position + 1)
+        } else {
+            None
+        };
+        let mut source = (self.source as u16) << 8 | position as u16;
+        // Above 0xDFFF the source wraps back into work RAM, like echo RAM
+        if source >= 0xE000 {
+            source -= 0x2000;
+        }
+        Some((source, 0xFE00 | position as u16))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cpu::dma::Dma;
+
+    #[test]
+    fn test_transfer() {
+        let mut dma = Dma::default();
+        assert_eq!(dma.tick(), None);
+        dma.start(0xC1);
+        assert_eq!(dma.tick(), Some((0xC100, 0xFE00)));
+        for _ in 1..0x9F {
+            dma.tick();
+        }
+        assert!(dma.active());
+        assert_eq!(dma.tick(), Some((0xC19F, 0xFE9F)));
+        assert!(!dma.active());
+        assert_eq!(dma.read(), 0xC1);
+
+        dma.start(0xFE);
+        assert_eq!(dma.tick(), Some((0xDE00, 0xFE00)));
+    }
+}
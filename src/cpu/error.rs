@@ -0,0 +1,30 @@
+use std::fmt;
+
+use crate::cpu::operand::Operand;
+
+/// Errors the emulator core can run into while stepping through a program.
+/// These are reported to the host instead of aborting the whole process.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmulatorError {
+    /// `opcode` at `pc` does not exist on the SM83. The CPU has locked up,
+    /// and only reports this the first time.
+    IllegalOpcode { opcode: u8, pc: u16 },
+    /// The instruction at `pc` tried to write to an operand that cannot be
+    /// written to, such as an immediate.
+    UnsupportedOperand { operand: Operand, pc: u16 },
+}
+
+impl fmt::Display for EmulatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmulatorError::IllegalOpcode { opcode, pc } => {
+                write!(f, "illegal opcode 0x{:02X} at 0x{:04X}", opcode, pc)
+            }
+            EmulatorError::UnsupportedOperand { operand, pc } => {
+                write!(f, "unsupported operand {} at 0x{:04X}", operand, pc)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EmulatorError {}
@@ -1,38 +1,39 @@
-use crate::cpu::arithmetic::unsigned_to_signed_16;
+use crate::cpu::error::EmulatorError;
 use crate::cpu::flag::Flag;
 use crate::cpu::flag::Flag::{C, H, N, Z};
+use crate::cpu::operand::Operand;
 use crate::cpu::registers::Register;
 use crate::cpu::registers::Register::{A, HL, PC, SP};
 use crate::cpu::value::{concat_values, Value};
 use crate::cpu::{MemoryLocation, CPU};
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RotateDirection {
     Right,
     Left,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Condition {
     FlagOn(Flag),
     FlagOff(Flag),
     None,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct JumpCycles {
     pub executed: u8,
     pub not_executed: u8,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AdditionalInstruction {
     Inc,
     Dec,
     None,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BitAddr {
     Zero,
     One,
@@ -58,7 +59,7 @@ impl BitAddr {
         }
     }
 
-    fn to_u8(&self) -> u8 {
+    pub fn to_u8(self) -> u8 {
         match self {
             BitAddr::Zero => 0,
             BitAddr::One => 1,
@@ -72,104 +73,110 @@ impl BitAddr {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Instruction {
     Load {
-        to: MemoryLocation,
-        what: Value,
+        to: Operand,
+        what: Operand,
         additional_instruction: AdditionalInstruction,
         cycles: u8,
         length: InstructionLength,
     },
     Add {
-        to: MemoryLocation,
-        what: Value,
+        to: Operand,
+        what: Operand,
         cycles: u8,
         length: InstructionLength,
     },
     Adc {
-        to: MemoryLocation,
-        what: Value,
+        to: Operand,
+        what: Operand,
+        cycles: u8,
+        length: InstructionLength,
+    },
+    AddSp {
+        to: Register,
+        offset: i8,
         cycles: u8,
         length: InstructionLength,
     },
     Sub {
-        from: MemoryLocation,
-        what: Value,
+        from: Operand,
+        what: Operand,
         cycles: u8,
         length: InstructionLength,
     },
     Sbc {
-        from: MemoryLocation,
-        what: Value,
+        from: Operand,
+        what: Operand,
         cycles: u8,
         length: InstructionLength,
     },
     Inc {
-        what: MemoryLocation,
+        what: Operand,
         cycles: u8,
     },
     Dec {
-        what: MemoryLocation,
+        what: Operand,
         cycles: u8,
     },
     Rot {
-        what: MemoryLocation,
+        what: Operand,
         direction: RotateDirection,
         use_carry: bool,
         cycles: u8,
         length: InstructionLength,
     },
     Shift {
-        what: MemoryLocation,
+        what: Operand,
         direction: RotateDirection,
         arithmetic: bool,
         cycles: u8,
         length: InstructionLength,
     },
     Jr {
-        how_far: Value,
+        how_far: i8,
         condition: Condition,
         cycles: JumpCycles,
         length: InstructionLength,
     },
     Jp {
-        to: Value,
+        to: Operand,
         condition: Condition,
         cycles: JumpCycles,
         length: InstructionLength,
     },
     And {
-        what: Value,
+        what: Operand,
         cycles: u8,
         length: InstructionLength,
     },
     Xor {
-        what: Value,
+        what: Operand,
         cycles: u8,
         length: InstructionLength,
     },
     Or {
-        what: Value,
+        what: Operand,
         cycles: u8,
         length: InstructionLength,
     },
-    Swap(MemoryLocation),
+    Swap(Operand),
     Cp {
-        what: Value,
+        what: Operand,
         cycles: u8,
         length: InstructionLength,
     },
     Bit {
-        what: MemoryLocation,
+        what: Operand,
         bit: BitAddr,
     },
     Res {
-        what: MemoryLocation,
+        what: Operand,
         bit: BitAddr,
     },
     Set {
-        what: MemoryLocation,
+        what: Operand,
         bit: BitAddr,
     },
     Daa,
@@ -180,14 +187,67 @@ pub enum Instruction {
     Reti,
     Pop(Register),
     Push(Register),
-    Call(Condition),
+    Call {
+        to: u16,
+        condition: Condition,
+    },
     Rst(BitAddr),
     Ei,
     Di,
+    Halt,
+    Stop,
+    Breakpoint,
+    Illegal(u8),
     Nop,
 }
 
-#[derive(Debug)]
+impl Instruction {
+    /// Number of bytes the instruction takes up, opcode included.
+    pub fn length(&self) -> InstructionLength {
+        match self {
+            Instruction::Load { length, .. }
+            | Instruction::Add { length, .. }
+            | Instruction::Adc { length, .. }
+            | Instruction::AddSp { length, .. }
+            | Instruction::Sub { length, .. }
+            | Instruction::Sbc { length, .. }
+            | Instruction::Rot { length, .. }
+            | Instruction::Shift { length, .. }
+            | Instruction::Jr { length, .. }
+            | Instruction::Jp { length, .. }
+            | Instruction::And { length, .. }
+            | Instruction::Xor { length, .. }
+            | Instruction::Or { length, .. }
+            | Instruction::Cp { length, .. } => *length,
+            // 0xCB prefixed
+            Instruction::Swap(_)
+            | Instruction::Bit { .. }
+            | Instruction::Res { .. }
+            | Instruction::Set { .. } => InstructionLength::Two,
+            Instruction::Stop => InstructionLength::Two,
+            Instruction::Call { .. } => InstructionLength::Three,
+            Instruction::Inc { .. }
+            | Instruction::Dec { .. }
+            | Instruction::Daa
+            | Instruction::Cpl
+            | Instruction::Scf
+            | Instruction::Ccf
+            | Instruction::Ret(_)
+            | Instruction::Reti
+            | Instruction::Pop(_)
+            | Instruction::Push(_)
+            | Instruction::Rst(_)
+            | Instruction::Ei
+            | Instruction::Di
+            | Instruction::Halt
+            | Instruction::Breakpoint
+            | Instruction::Illegal(_)
+            | Instruction::Nop => InstructionLength::One,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum InstructionLength {
     One,
     Two,
@@ -205,7 +265,7 @@ impl InstructionLength {
 }
 
 impl CPU {
-    pub fn execute(&mut self, instruction: Instruction) {
+    pub fn execute(&mut self, instruction: Instruction) -> Result<(), EmulatorError> {
         match instruction {
             Instruction::Load {
                 to,
@@ -214,10 +274,8 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                match to {
-                    MemoryLocation::Register(reg) => self.registers.set(reg, what),
-                    MemoryLocation::Pointer(addr) => self.write(addr, what),
-                };
+                let value = self.read_operand(what);
+                self.write_operand(to, value)?;
                 match additional_instruction {
                     AdditionalInstruction::Inc => {
                         self.registers.set(HL, self.registers.get(HL) + 1u8)
@@ -236,10 +294,20 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                if let MemoryLocation::Register(reg) = to {
-                    let result = self.add(self.registers.get(reg), what);
-                    self.registers.set(reg, result);
-                };
+                let to = self.location(to)?;
+                let result = self.add(self.read_location(to), self.read_operand(what));
+                self.write_location(to, result);
+                self.inc_clock(cycles);
+                self.registers.inc_pc(length.count());
+            }
+            Instruction::AddSp {
+                to,
+                offset,
+                cycles,
+                length,
+            } => {
+                let result = self.add_sp_signed(self.registers.get(SP), offset);
+                self.registers.set(to, result);
                 self.inc_clock(cycles);
                 self.registers.inc_pc(length.count());
             }
@@ -249,19 +317,9 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                let carry_flag_value = if self.registers.f.is_set(C) {
-                    Value::EightBit(1)
-                } else {
-                    Value::EightBit(0)
-                };
-                let operand_with_carry = self.add(what, carry_flag_value);
-                match to {
-                    MemoryLocation::Register(reg) => {
-                        let result = self.add(self.registers.get(reg), operand_with_carry);
-                        self.registers.set(reg, result);
-                    }
-                    _ => panic!("NOT IMPLEMENTED!!!!"),
-                };
+                let to = self.location(to)?;
+                let result = self.adc(self.read_location(to), self.read_operand(what));
+                self.write_location(to, result);
                 self.inc_clock(cycles);
                 self.registers.inc_pc(length.count());
             }
@@ -271,10 +329,9 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                if let MemoryLocation::Register(reg) = from {
-                    let result = self.sub(self.registers.get(reg), what);
-                    self.registers.set(reg, result);
-                };
+                let from = self.location(from)?;
+                let result = self.sub(self.read_location(from), self.read_operand(what));
+                self.write_location(from, result);
                 self.inc_clock(cycles);
                 self.registers.inc_pc(length.count());
             }
@@ -284,51 +341,30 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                let carry_flag_value = if self.registers.f.is_set(C) {
-                    Value::EightBit(1)
-                } else {
-                    Value::EightBit(0)
-                };
-                let operand_with_carry = self.add(what, carry_flag_value);
-                match from {
-                    MemoryLocation::Register(reg) => {
-                        let result = self.sub(self.registers.get(reg), operand_with_carry);
-                        self.registers.set(reg, result);
-                    }
-                    _ => panic!("NOT IMPLEMENTED!!!!"),
-                };
-                self.registers.f.set(N);
+                let from = self.location(from)?;
+                let result = self.sbc(self.read_location(from), self.read_operand(what));
+                self.write_location(from, result);
                 self.inc_clock(cycles);
                 self.registers.inc_pc(length.count());
             }
             Instruction::Inc { what, cycles } => {
-                match what {
-                    MemoryLocation::Register(reg) => {
-                        let result = if reg.is_eight_bits() {
-                            self.add(self.registers.get(reg), Value::EightBit(1))
-                        } else {
-                            self.add(self.registers.get(reg), Value::SixteenBit(1))
-                        };
-                        self.registers.set(reg, result);
-                    }
-                    _ => panic!("NOT IMPLEMENTED!!!!"),
+                let what = self.location(what)?;
+                let result = match self.read_location(what) {
+                    val @ Value::EightBit(_) => self.inc(val),
+                    // INC rr leaves the flags alone
+                    val @ Value::SixteenBit(_) => val + 1u16,
                 };
+                self.write_location(what, result);
                 self.inc_clock(cycles);
                 self.registers.inc_pc(1);
             }
             Instruction::Dec { what, cycles } => {
-                match what {
-                    MemoryLocation::Register(reg) => {
-                        let result = if reg.is_eight_bits() {
-                            self.sub(self.registers.get(reg), Value::EightBit(1))
-                        } else {
-                            self.sub(self.registers.get(reg), Value::SixteenBit(1))
-                        };
-                        self.registers.set(reg, result);
-                    }
-                    _ => panic!("NOT IMPLEMENTED!!!!"),
+                let what = self.location(what)?;
+                let result = match self.read_location(what) {
+                    val @ Value::EightBit(_) => self.dec(val),
+                    val @ Value::SixteenBit(_) => val - 1u16,
                 };
-                self.registers.f.set(N);
+                self.write_location(what, result);
                 self.inc_clock(cycles);
                 self.registers.inc_pc(1);
             }
@@ -339,7 +375,7 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                match what {
+                match self.location(what)? {
                     MemoryLocation::Register(reg) => {
                         let val = self.registers.get(reg);
                         let result = match direction {
@@ -367,7 +403,7 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                match what {
+                match self.location(what)? {
                     MemoryLocation::Register(reg) => {
                         let val = self.registers.get(reg);
                         let result = match direction {
@@ -395,7 +431,8 @@ impl CPU {
                 length,
             } => {
                 if self.condition_passes(condition) {
-                    let new_location = self.registers.get(PC) + unsigned_to_signed_16(how_far);
+                    // Relative to the end of the JR itself
+                    let new_location = self.registers.get(PC) + length.count() + i16::from(how_far);
                     self.registers.set(PC, new_location);
                     self.inc_clock(cycles.executed);
                 } else {
@@ -410,7 +447,7 @@ impl CPU {
                 length,
             } => {
                 if self.condition_passes(condition) {
-                    self.registers.set(PC, to);
+                    self.registers.set(PC, self.read_operand(to));
                     self.inc_clock(cycles.executed);
                 } else {
                     self.registers.inc_pc(length.count());
@@ -422,7 +459,7 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                let result = self.registers.get(A) & what;
+                let result = self.registers.get(A) & self.read_operand(what);
                 self.registers.set(A, result);
                 // Apparently H is always set when running AND
                 self.registers.f.set(H);
@@ -437,7 +474,7 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                let result = self.registers.get(A) ^ what;
+                let result = self.registers.get(A) ^ self.read_operand(what);
                 self.registers.set(A, result);
                 self.registers.f.unset(N);
                 self.registers.f.unset(C);
@@ -451,7 +488,7 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                let result = self.registers.get(A) | what;
+                let result = self.registers.get(A) | self.read_operand(what);
                 self.registers.set(A, result);
                 self.check_zero_flag(result);
                 self.registers.f.unset(N);
@@ -461,7 +498,7 @@ impl CPU {
                 self.inc_clock(cycles);
             }
             Instruction::Swap(what) => {
-                match what {
+                match self.location(what)? {
                     MemoryLocation::Register(reg) => {
                         let val = self.registers.get(reg);
                         let result = val.swap();
@@ -484,7 +521,7 @@ impl CPU {
                 cycles,
                 length,
             } => {
-                let result = self.sub(self.registers.get(A), what);
+                let result = self.sub(self.registers.get(A), self.read_operand(what));
                 self.check_zero_flag(result);
                 self.registers.inc_pc(length.count());
                 self.inc_clock(cycles);
@@ -540,7 +577,7 @@ impl CPU {
                 self.registers.inc_pc(1);
             }
             Instruction::Bit { what, bit } => {
-                let is_one = match what {
+                let is_one = match self.location(what)? {
                     MemoryLocation::Register(reg) => {
                         self.inc_clock(2);
                         self.registers.get(reg) & Value::EightBit(1 << bit.to_u8())
@@ -563,7 +600,7 @@ impl CPU {
                 self.registers.inc_pc(2);
             }
             Instruction::Res { what, bit } => {
-                match what {
+                match self.location(what)? {
                     MemoryLocation::Register(reg) => {
                         let result = self.registers.get(reg) & !Value::EightBit(1 << bit.to_u8());
                         self.registers.set(reg, result);
@@ -578,7 +615,7 @@ impl CPU {
                 self.registers.inc_pc(2);
             }
             Instruction::Set { what, bit } => {
-                match what {
+                match self.location(what)? {
                     MemoryLocation::Register(reg) => {
                         let result = self.registers.get(reg) | Value::EightBit(1 << bit.to_u8());
                         self.registers.set(reg, result);
@@ -639,7 +676,7 @@ impl CPU {
                 self.inc_clock(4);
                 self.registers.inc_pc(1);
             }
-            Instruction::Call(condition) => {
+            Instruction::Call { to, condition } => {
                 let pc_before_execution = self.registers.get(PC);
                 self.registers.set(PC, pc_before_execution + 3u16);
 
@@ -650,8 +687,7 @@ impl CPU {
                     self.registers.set(SP, self.registers.get(SP) - 1u16);
                     self.write(self.registers.get(SP), self.registers.get(PC).low_byte());
 
-                    self.registers
-                        .set(PC, self.read(pc_before_execution + 1u16, true));
+                    self.registers.set(PC, Value::SixteenBit(to));
 
                     self.inc_clock(6);
                 } else {
@@ -677,13 +713,40 @@ impl CPU {
             }
             Instruction::Di => {
                 self.unset_ime();
+                self.unset_ime_next();
                 self.registers.inc_pc(1);
                 self.inc_clock(1)
             }
+            Instruction::Halt => {
+                self.inc_clock(1);
+                self.registers.inc_pc(1);
+                self.halt();
+            }
+            Instruction::Stop => {
+                // STOP is followed by a second byte which is ignored
+                self.inc_clock(1);
+                self.registers.inc_pc(2);
+                self.stop();
+            }
+            Instruction::Breakpoint => {
+                self.inc_clock(1);
+                self.registers.inc_pc(1);
+                self.set_breakpoint();
+            }
+            Instruction::Illegal(opcode) => {
+                // The CPU hangs on the illegal opcode, PC never moves again
+                self.inc_clock(1);
+                self.lock();
+                return Err(EmulatorError::IllegalOpcode {
+                    opcode,
+                    pc: self.registers.get(PC).extract(),
+                });
+            }
             Instruction::Nop => {
                 self.inc_clock(1);
                 self.registers.inc_pc(1);
             }
         }
+        Ok(())
     }
 }
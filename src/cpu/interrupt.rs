@@ -0,0 +1,90 @@
+use crate::cpu::registers::Register::{PC, SP};
+use crate::cpu::value::Value;
+use crate::cpu::CPU;
+
+/// Interrupt sources, in priority order. The discriminant is the bit
+/// index each one occupies in IF (0xFF0F) and IE (0xFFFF).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interrupt {
+    VBlank,
+    LcdStat,
+    Timer,
+    Serial,
+    Joypad,
+}
+
+impl Interrupt {
+    /// Highest priority first
+    pub const ALL: [Interrupt; 5] = [
+        Interrupt::VBlank,
+        Interrupt::LcdStat,
+        Interrupt::Timer,
+        Interrupt::Serial,
+        Interrupt::Joypad,
+    ];
+
+    pub fn bit(&self) -> u8 {
+        match self {
+            Interrupt::VBlank => 1 << 0,
+            Interrupt::LcdStat => 1 << 1,
+            Interrupt::Timer => 1 << 2,
+            Interrupt::Serial => 1 << 3,
+            Interrupt::Joypad => 1 << 4,
+        }
+    }
+
+    pub fn vector(&self) -> Value {
+        match self {
+            Interrupt::VBlank => Value::SixteenBit(0x0040),
+            Interrupt::LcdStat => Value::SixteenBit(0x0048),
+            Interrupt::Timer => Value::SixteenBit(0x0050),
+            Interrupt::Serial => Value::SixteenBit(0x0058),
+            Interrupt::Joypad => Value::SixteenBit(0x0060),
+        }
+    }
+}
+
+impl CPU {
+    /// Sets the interrupt's bit in IF.
+    pub fn request_interrupt(&mut self, interrupt: Interrupt) {
+        self.memory_bus.request_interrupt(interrupt);
+    }
+
+    /// The highest priority interrupt which is both requested (IF) and
+    /// enabled (IE), regardless of IME.
+    pub fn pending_interrupt(&self) -> Option<Interrupt> {
+        let pending = self.memory_bus.read(0xFFFF) & self.memory_bus.read(0xFF0F);
+        Interrupt::ALL
+            .into_iter()
+            .find(|interrupt| pending & interrupt.bit() != 0)
+    }
+
+    pub fn interrupt_pending(&self) -> bool {
+        self.pending_interrupt().is_some()
+    }
+
+    /// Called between instructions. If IME is set and an interrupt is
+    /// pending, IME is cleared along with the interrupt's IF bit, PC is
+    /// pushed and execution continues at the interrupt's vector.
+    /// The whole dispatch takes 5 M-cycles.
+    ///
+    /// Returns the interrupt which was serviced, if any.
+    pub fn handle_interrupts(&mut self) -> Option<Interrupt> {
+        if !self.ime() {
+            return None;
+        }
+        let interrupt = self.pending_interrupt()?;
+        self.unset_ime();
+        self.memory_bus.acknowledge_interrupt(interrupt);
+
+        self.registers.set(SP, self.registers.get(SP) - 1u16);
+        self.write(self.registers.get(SP), self.registers.get(PC).high_byte());
+
+        self.registers.set(SP, self.registers.get(SP) - 1u16);
+        self.write(self.registers.get(SP), self.registers.get(PC).low_byte());
+
+        self.registers.set(PC, interrupt.vector());
+        self.inc_clock(5);
+        Some(interrupt)
+    }
+}
@@ -0,0 +1,71 @@
+/// The parts of the Game Boy that own registers in 0xFF00-0xFF7F.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Component {
+    Joypad,
+    Serial,
+    Timer,
+    Interrupt,
+    Audio,
+    Ppu,
+    Dma,
+    // CGB speed switch, KEY1
+    Speed,
+}
+
+/// How one I/O register looks from the CPU's side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IoRegister {
+    pub component: Component,
+    // Bits that always read as 1, because they are unused or write-only
+    pub read_mask: u8,
+    // Bits the CPU can change, the rest are read-only
+    pub write_mask: u8,
+}
+
+impl IoRegister {
+    /// None for addresses nothing answers to. Those read as 0xFF and
+    /// ignore writes.
+    pub fn at(address: u16) -> Option<IoRegister> {
+        use Component::*;
+
+        let (component, read_mask, write_mask) = match address {
+            // P1/JOYP, where only the matrix select is writable
+            0xFF00 => (Joypad, 0xC0, 0x30),
+            // SB, SC
+            0xFF01 => (Serial, 0x00, 0xFF),
+            0xFF02 => (Serial, 0x7E, 0x81),
+            // DIV, TIMA, TMA, TAC
+            0xFF04..=0xFF06 => (Timer, 0x00, 0xFF),
+            0xFF07 => (Timer, 0xF8, 0x07),
+            0xFF0F => (Interrupt, 0xE0, 0x1F),
+            // Sound, where most of the channel settings are write-only
+            0xFF10 => (Audio, 0x80, 0xFF),
+            0xFF11 | 0xFF16 => (Audio, 0x3F, 0xFF),
+            0xFF12 | 0xFF17 | 0xFF21..=0xFF22 | 0xFF24..=0xFF25 => (Audio, 0x00, 0xFF),
+            0xFF13 | 0xFF18 | 0xFF1B | 0xFF1D | 0xFF20 => (Audio, 0xFF, 0xFF),
+            0xFF14 | 0xFF19 | 0xFF1E | 0xFF23 => (Audio, 0xBF, 0xFF),
+            0xFF1A => (Audio, 0x7F, 0xFF),
+            0xFF1C => (Audio, 0x9F, 0xFF),
+            // NR52, where the channel status bits are read-only
+            0xFF26 => (Audio, 0x70, 0x80),
+            // Wave pattern RAM
+            0xFF30..=0xFF3F => (Audio, 0x00, 0xFF),
+            // LCDC, STAT, SCY, SCX, LY, LYC
+            0xFF40 => (Ppu, 0x00, 0xFF),
+            0xFF41 => (Ppu, 0x80, 0x78),
+            0xFF42..=0xFF43 => (Ppu, 0x00, 0xFF),
+            0xFF44 => (Ppu, 0x00, 0x00),
+            0xFF45 => (Ppu, 0x00, 0xFF),
+            0xFF46 => (Dma, 0x00, 0xFF),
+            // BGP, OBP0, OBP1, WY, WX
+            0xFF47..=0xFF4B => (Ppu, 0x00, 0xFF),
+            0xFF4D => (Speed, 0x7E, 0x01),
+            _ => return None,
+        };
+        Some(IoRegister {
+            component,
+            read_mask,
+            write_mask,
+        })
+    }
+}
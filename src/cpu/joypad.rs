@@ -0,0 +1,98 @@
+use crate::cpu::CPU;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Button {
+    Right,
+    Left,
+    Up,
+    Down,
+    A,
+    B,
+    Select,
+    Start,
+}
+
+impl Button {
+    /// The button's bit in a joypad state, with the d-pad in the low
+    /// nibble in the same order as P1.
+    pub fn bit(&self) -> u8 {
+        match self {
+            Button::Right => 1 << 0,
+            Button::Left => 1 << 1,
+            Button::Up => 1 << 2,
+            Button::Down => 1 << 3,
+            Button::A => 1 << 4,
+            Button::B => 1 << 5,
+            Button::Select => 1 << 6,
+            Button::Start => 1 << 7,
+        }
+    }
+}
+
+/// P1/JOYP. Bits 4 and 5 pick which half of the button matrix drives the
+/// input lines, which go low for pressed buttons.
+#[derive(Debug, Default)]
+pub struct Joypad {
+    // Bits 4-5 as last written, 0 selects
+    select: u8,
+    // One bit per button, 1 when pressed
+    pressed: u8,
+}
+
+impl Joypad {
+    pub(crate) fn read(&self) -> u8 {
+        0xC0 | self.select | self.lines()
+    }
+
+    // All of these return whether an input line went from high to low,
+    // which is what requests the joypad interrupt
+    pub(crate) fn write(&mut self, data: u8) -> bool {
+        let before = self.lines();
+        self.select = data & 0x30;
+        self.fell(before)
+    }
+
+    pub(crate) fn set_state(&mut self, pressed: u8) -> bool {
+        let before = self.lines();
+        self.pressed = pressed;
+        self.fell(before)
+    }
+
+    pub(crate) fn pressed(&self) -> u8 {
+        self.pressed
+    }
+
+    // P10-P13, low when a selected button is pressed
+    fn lines(&self) -> u8 {
+        let mut lines = 0x0F;
+        if self.select & 0x10 == 0 {
+            lines &= !self.pressed & 0x0F;
+        }
+        if self.select & 0x20 == 0 {
+            lines &= !(self.pressed >> 4) & 0x0F;
+        }
+        lines
+    }
+
+    fn fell(&self, before: u8) -> bool {
+        before & !self.lines() != 0
+    }
+}
+
+impl CPU {
+    pub fn press(&mut self, button: Button) {
+        let pressed = self.memory_bus.joypad_state() | button.bit();
+        self.set_joypad_state(pressed);
+    }
+
+    pub fn release(&mut self, button: Button) {
+        let pressed = self.memory_bus.joypad_state() & !button.bit();
+        self.set_joypad_state(pressed);
+    }
+
+    /// Sets every button at once, one bit each as given by `Button::bit`.
+    /// Handy for replaying recorded input.
+    pub fn set_joypad_state(&mut self, pressed: u8) {
+        self.memory_bus.set_joypad_state(pressed);
+    }
+}
@@ -1,68 +1,230 @@
+use crate::cartridge::header::CgbSupport;
+use crate::cartridge::Cartridge;
+use crate::cpu::dma::Dma;
+use crate::cpu::interrupt::Interrupt;
+use crate::cpu::io::{Component, IoRegister};
+use crate::cpu::joypad::Joypad;
+use crate::cpu::timer::Timer;
+use crate::ppu::{Ppu, Renderer};
+
 #[derive(Debug)]
 pub struct MemoryBus {
+    // When present, owns 0x0000-0x7FFF and 0xA000-0xBFFF. Without one the
+    // ROM area is plain RAM, which is handy for poking in test programs.
+    cartridge: Option<Cartridge>,
     rom_bank_00: [u8; 0x4000],         // 16 KiB ROM bank 00
     rom_bank_01: [u8; 0x4000],         // 16 KiB ROM Bank 01–NN
-    vram: [u8; 0x2000],                // 8 KiB Video RAM (VRAM)
     external_ram: [u8; 0x2000],        // 8 KiB External RAM
     work_ram: [u8; 0x1000],            // 4 KiB Work RAM (WRAM)
     work_ram_switchable: [u8; 0x1000], // 4 KiB Work RAM (WRAM), switchable bank 1–7
-    echo_ram: [u8; 0x1E00],            // Echo RAM (mirror of C000–DDFF)
-    oam: [u8; 0xA0],                   // Object attribute memory (OAM)
     io_registers: [u8; 0x80],          // I/O Registers
+    joypad: Joypad,                    // P1/JOYP
+    timer: Timer,                      // DIV, TIMA, TMA, TAC
+    ppu: Ppu,                          // VRAM, OAM and the LCD registers
+    dma: Dma,                          // OAM DMA
     hram: [u8; 0x7F],                  // High RAM (HRAM)
+    interrupt_flag: u8,                // Interrupt Flag register (IF)
     interrupt_enable: u8,              // Interrupt Enable register (IE)
 }
 
 impl Default for MemoryBus {
     fn default() -> Self {
         MemoryBus {
+            cartridge: None,
             rom_bank_00: [0; 0x4000],
             rom_bank_01: [0; 0x4000],
-            vram: [0; 0x2000],
             external_ram: [0; 0x2000],
             work_ram: [0; 0x1000],
             work_ram_switchable: [0; 0x1000],
-            echo_ram: [0; 0x1E00],
-            oam: [0; 0xA0],
             io_registers: [0; 0x80],
+            joypad: Joypad::default(),
+            timer: Timer::default(),
+            ppu: Ppu::default(),
+            dma: Dma::default(),
             hram: [0; 0x7F],
+            interrupt_flag: 0u8,
             interrupt_enable: 0u8,
         }
     }
 }
 
 impl MemoryBus {
+    pub fn new(renderer: Renderer) -> MemoryBus {
+        MemoryBus {
+            ppu: Ppu::new(renderer),
+            ..MemoryBus::default()
+        }
+    }
+
+    pub fn with_cartridge(cartridge: Cartridge, renderer: Renderer) -> MemoryBus {
+        MemoryBus {
+            cartridge: Some(cartridge),
+            ..MemoryBus::new(renderer)
+        }
+    }
+
+    pub fn cartridge(&self) -> Option<&Cartridge> {
+        self.cartridge.as_ref()
+    }
+
+    pub fn cartridge_mut(&mut self) -> Option<&mut Cartridge> {
+        self.cartridge.as_mut()
+    }
+
+    /// Whether the cartridge asks for CGB mode. Without that, the CGB-only
+    /// registers aren't there.
+    pub(crate) fn cgb_mode(&self) -> bool {
+        self.cartridge
+            .as_ref()
+            .is_some_and(|cartridge| cartridge.header().cgb != CgbSupport::None)
+    }
+
+    pub fn ppu(&self) -> &Ppu {
+        &self.ppu
+    }
+
+    // Called once per M-cycle
+    pub(crate) fn tick(&mut self, double_speed: bool) {
+        if self.timer.tick() {
+            self.request_interrupt(Interrupt::Timer);
+        }
+        if let Some((source, destination)) = self.dma.tick() {
+            let data = self.read_bus(source);
+            self.ppu.write_oam(destination, data);
+        }
+        // The PPU runs at the same rate whatever the CPU speed
+        self.interrupt_flag |= self.ppu.tick(if double_speed { 2 } else { 4 });
+        if let Some(cartridge) = &mut self.cartridge {
+            cartridge.tick(double_speed);
+        }
+    }
+
     pub(crate) fn read(&self, address: u16) -> u8 {
+        // OAM DMA holds the buses, which leaves the CPU with HRAM and the
+        // registers around it. Everything else reads as 0xFF.
+        if self.dma.active() && address < 0xFF00 {
+            return 0xFF;
+        }
+        self.read_bus(address)
+    }
+
+    pub(crate) fn write(&mut self, address: u16, data: u8) {
+        if self.dma.active() && address < 0xFF00 {
+            return;
+        }
+        self.write_bus(address, data);
+    }
+
+    fn read_bus(&self, address: u16) -> u8 {
+        if let Some(cartridge) = &self.cartridge {
+            if let 0x0000..=0x7FFF | 0xA000..=0xBFFF = address {
+                return cartridge.read(address);
+            }
+        }
         match address {
             0x0000..=0x3FFF => self.rom_bank_00[address as usize],
             0x4000..=0x7FFF => self.rom_bank_01[address as usize - 0x4000],
-            0x8000..=0x9FFF => self.vram[address as usize - 0x8000],
+            0x8000..=0x9FFF => self.ppu.read_vram(address),
             0xA000..=0xBFFF => self.external_ram[address as usize - 0xA000],
             0xC000..=0xCFFF => self.work_ram[address as usize - 0xC000],
             0xD000..=0xDFFF => self.work_ram_switchable[address as usize - 0xD000],
-            0xE000..=0xFDFF => self.echo_ram[address as usize - 0xE000],
-            0xFE00..=0xFE9F => self.oam[address as usize - 0xFE00],
-            0xFF00..=0xFF7F => self.io_registers[address as usize - 0xFF00],
+            // Echo RAM, a mirror of C000–DDFF
+            0xE000..=0xFDFF => self.read_bus(address - 0x2000),
+            0xFE00..=0xFE9F => self.ppu.read_oam(address),
+            // Not usable, reads as 0 on DMG
+            0xFEA0..=0xFEFF => 0x00,
+            0xFF00..=0xFF7F => self.read_io(address),
             0xFF80..=0xFFFE => self.hram[address as usize - 0xFF80],
             0xFFFF => self.interrupt_enable,
-            _ => 0,
         }
     }
 
-    pub(crate) fn write(&mut self, address: u16, data: u8) {
+    fn write_bus(&mut self, address: u16, data: u8) {
+        // ROM is read-only, writes there are commands for the mapper
+        if let Some(cartridge) = &mut self.cartridge {
+            if let 0x0000..=0x7FFF | 0xA000..=0xBFFF = address {
+                cartridge.write(address, data);
+                return;
+            }
+        }
         match address {
             0x0000..=0x3FFF => self.rom_bank_00[address as usize] = data,
             0x4000..=0x7FFF => self.rom_bank_01[address as usize - 0x4000] = data,
-            0x8000..=0x9FFF => self.vram[address as usize - 0x8000] = data,
+            0x8000..=0x9FFF => self.ppu.write_vram(address, data),
             0xA000..=0xBFFF => self.external_ram[address as usize - 0xA000] = data,
             0xC000..=0xCFFF => self.work_ram[address as usize - 0xC000] = data,
             0xD000..=0xDFFF => self.work_ram_switchable[address as usize - 0xD000] = data,
-            0xE000..=0xFDFF => self.echo_ram[address as usize - 0xE000] = data,
-            0xFE00..=0xFE9F => self.oam[address as usize - 0xFE00] = data,
-            0xFF00..=0xFF7F => self.io_registers[address as usize - 0xFF00] = data,
+            0xE000..=0xFDFF => self.write_bus(address - 0x2000, data),
+            0xFE00..=0xFE9F => self.ppu.write_oam(address, data),
+            // Not usable, writes are ignored
+            0xFEA0..=0xFEFF => {}
+            0xFF00..=0xFF7F => self.write_io(address, data),
             0xFF80..=0xFFFE => self.hram[address as usize - 0xFF80] = data,
             0xFFFF => self.interrupt_enable = data,
-            _ => panic!("Invalid memory address: 0x{:04X}", address),
         }
     }
+
+    fn read_io(&self, address: u16) -> u8 {
+        let register = match IoRegister::at(address) {
+            Some(register) => register,
+            None => return 0xFF,
+        };
+        let data = match register.component {
+            Component::Interrupt => self.interrupt_flag,
+            Component::Joypad => self.joypad.read(),
+            Component::Timer => self.timer.read(address),
+            Component::Ppu => self.ppu.read_register(address),
+            Component::Dma => self.dma.read(),
+            Component::Speed if !self.cgb_mode() => return 0xFF,
+            _ => self.io_registers[address as usize - 0xFF00],
+        };
+        data | register.read_mask
+    }
+
+    fn write_io(&mut self, address: u16, data: u8) {
+        let register = match IoRegister::at(address) {
+            Some(register) => register,
+            None => return,
+        };
+        let data = data & register.write_mask;
+        match register.component {
+            Component::Interrupt => self.interrupt_flag = data,
+            Component::Joypad => {
+                if self.joypad.write(data) {
+                    self.request_interrupt(Interrupt::Joypad);
+                }
+            }
+            Component::Timer => self.timer.write(address, data),
+            Component::Ppu => self.interrupt_flag |= self.ppu.write_register(address, data),
+            Component::Dma => self.dma.start(data),
+            Component::Speed if !self.cgb_mode() => {}
+            _ => {
+                let old = self.io_registers[address as usize - 0xFF00];
+                self.io_registers[address as usize - 0xFF00] = old & !register.write_mask | data;
+            }
+        }
+    }
+
+    pub(crate) fn joypad_state(&self) -> u8 {
+        self.joypad.pressed()
+    }
+
+    pub(crate) fn set_joypad_state(&mut self, pressed: u8) {
+        if self.joypad.set_state(pressed) {
+            self.request_interrupt(Interrupt::Joypad);
+        }
+    }
+
+    // Bit 7 of KEY1, which the CPU can't write itself
+    pub(crate) fn set_double_speed(&mut self, double_speed: bool) {
+        self.io_registers[0x4D] = if double_speed { 0x80 } else { 0x00 };
+    }
+
+    pub(crate) fn request_interrupt(&mut self, interrupt: Interrupt) {
+        self.interrupt_flag |= interrupt.bit();
+    }
+
+    pub(crate) fn acknowledge_interrupt(&mut self, interrupt: Interrupt) {
+        self.interrupt_flag &= !interrupt.bit();
+    }
 }
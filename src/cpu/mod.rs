@@ -1,14 +1,26 @@
+use crate::cartridge::error::CartridgeError;
+use crate::cartridge::Cartridge;
+use crate::cpu::error::EmulatorError;
 use crate::cpu::memory_bus::MemoryBus;
 use crate::cpu::registers::Register::PC;
 use crate::cpu::registers::{Register, Registers};
 use crate::cpu::value::Value;
+use crate::ppu::frame::Frame;
+use crate::ppu::Renderer;
 
 pub mod arithmetic;
+pub mod dma;
+pub mod error;
 pub mod flag;
 pub mod instruction;
+pub mod interrupt;
+pub mod io;
+pub mod joypad;
 pub mod memory_bus;
 pub mod opcode;
+pub mod operand;
 pub mod registers;
+pub mod timer;
 pub mod value;
 
 #[derive(Default, Debug)]
@@ -17,6 +29,12 @@ pub struct CPU {
     memory_bus: MemoryBus,
     ime: bool,
     ime_next: bool,
+    halted: bool,
+    halt_bug: bool,
+    stopped: bool,
+    locked: bool,
+    breakpoint: bool,
+    double_speed: bool,
     clock: u64,
 }
 
@@ -29,6 +47,40 @@ pub enum MemoryLocation {
 // Ensure that these read and write commands are correct.
 // I think they are, but the Endianness is a little confusing.
 impl CPU {
+    /// A CPU whose PPU draws with the given renderer. `default` uses the
+    /// scanline one.
+    pub fn new(renderer: Renderer) -> CPU {
+        CPU {
+            memory_bus: MemoryBus::new(renderer),
+            ..CPU::default()
+        }
+    }
+
+    /// Swaps in a fresh memory bus that owns the whole ROM image, rather
+    /// than copying it into the address space. The renderer carries over.
+    pub fn load_cartridge(&mut self, rom: Vec<u8>) -> Result<(), CartridgeError> {
+        let renderer = self.memory_bus.ppu().renderer();
+        self.memory_bus = MemoryBus::with_cartridge(Cartridge::new(rom)?, renderer);
+        Ok(())
+    }
+
+    pub fn cartridge(&self) -> Option<&Cartridge> {
+        self.memory_bus.cartridge()
+    }
+
+    pub fn cartridge_mut(&mut self) -> Option<&mut Cartridge> {
+        self.memory_bus.cartridge_mut()
+    }
+
+    /// The last picture the PPU finished.
+    pub fn frame(&self) -> &Frame {
+        self.memory_bus.ppu().frame()
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.memory_bus.ppu().frame_count()
+    }
+
     pub fn read(&self, addr: Value, two_bytes: bool) -> Value {
         let val = match addr {
             Value::EightBit(a) => a as u16,
@@ -59,6 +111,97 @@ impl CPU {
         }
     }
 
+    /// Reads a register, or the byte a pointer refers to.
+    pub fn read_location(&self, location: MemoryLocation) -> Value {
+        match location {
+            MemoryLocation::Register(reg) => self.registers.get(reg),
+            MemoryLocation::Pointer(addr) => self.read(addr, false),
+        }
+    }
+
+    pub fn write_location(&mut self, location: MemoryLocation, data: Value) {
+        match location {
+            MemoryLocation::Register(reg) => self.registers.set(reg, data),
+            MemoryLocation::Pointer(addr) => self.write(addr, data),
+        }
+    }
+
+    /// Runs the CPU for a single instruction and returns the number of
+    /// M-cycles it took. A pending interrupt is serviced instead of
+    /// fetching, and a halted, stopped or locked CPU only idles for one
+    /// cycle (none at all while stopped). Running into an illegal opcode is
+    /// an error once, after which the CPU just idles, locked.
+    pub fn step(&mut self) -> Result<u64, EmulatorError> {
+        // EI takes effect once the instruction after it is done, unless that
+        // instruction is a DI
+        let enable_ime = self.ime_next;
+        let cycles = self.step_instruction();
+        if enable_ime && self.ime_next {
+            self.set_ime();
+            self.unset_ime_next();
+        }
+        cycles
+    }
+
+    fn step_instruction(&mut self) -> Result<u64, EmulatorError> {
+        let start = self.clock;
+        self.breakpoint = false;
+        if self.locked {
+            self.tick_locked();
+            return Ok(self.clock - start);
+        }
+        if self.stopped {
+            self.tick_stopped();
+            if self.stopped {
+                return Ok(0);
+            }
+        }
+        if self.halted {
+            self.tick_halted();
+            if self.halted {
+                return Ok(self.clock - start);
+            }
+        }
+        if self.handle_interrupts().is_none() {
+            if let Value::EightBit(code) = self.read(self.registers.get(PC), false) {
+                let instruction = self.lookup(code);
+                self.execute(instruction)?;
+            }
+        }
+        Ok(self.clock - start)
+    }
+
+    /// Steps until at least `cycles` M-cycles have gone by, and returns how
+    /// many actually did, since the last instruction may overshoot. Gives
+    /// up early if the CPU gets stopped, as only input can wake it again,
+    /// or is locked up for good.
+    pub fn run_for_cycles(&mut self, cycles: u64) -> Result<u64, EmulatorError> {
+        let mut elapsed = 0;
+        while elapsed < cycles {
+            elapsed += self.step()?;
+            if self.stopped || self.locked {
+                break;
+            }
+        }
+        Ok(elapsed)
+    }
+
+    /// Steps until `predicate` holds (checked after every step), and
+    /// returns the number of M-cycles that took. Like `run_for_cycles`, it
+    /// also gives up if the CPU gets stopped or locked.
+    pub fn run_until<F>(&mut self, mut predicate: F) -> Result<u64, EmulatorError>
+    where
+        F: FnMut(&CPU) -> bool,
+    {
+        let mut elapsed = 0;
+        loop {
+            elapsed += self.step()?;
+            if predicate(self) || self.stopped || self.locked {
+                return Ok(elapsed);
+            }
+        }
+    }
+
     pub fn immediate_operand(&self, two_bytes: bool) -> Value {
         if two_bytes {
             self.read(self.registers.get(PC) + Value::SixteenBit(1), true)
@@ -70,10 +213,7 @@ impl CPU {
     pub fn inc_clock(&mut self, cycles: u8) {
         for _ in 0..cycles {
             self.clock += 1;
-            if self.ime_next {
-                self.set_ime();
-                self.unset_ime_next();
-            }
+            self.memory_bus.tick(self.double_speed);
         }
     }
 
@@ -100,6 +240,100 @@ impl CPU {
     pub fn ime(&self) -> bool {
         self.ime
     }
+
+    pub fn halt(&mut self) {
+        // With IME off and an interrupt already pending, the DMG never
+        // enters HALT. Instead the following opcode byte is read twice.
+        if !self.ime && self.interrupt_pending() {
+            self.halt_bug = true;
+        } else {
+            self.halted = true;
+        }
+    }
+
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Runs one M-cycle of a halted CPU. Fetching is suspended, but the
+    /// clock keeps ticking until an interrupt is pending. IME plays no part
+    /// here: a pending interrupt wakes the CPU even when it will not be
+    /// serviced.
+    pub fn tick_halted(&mut self) {
+        if self.interrupt_pending() {
+            self.halted = false;
+        } else {
+            self.inc_clock(1);
+        }
+    }
+
+    pub fn stop(&mut self) {
+        // Writing DIV resets it, whatever value is written
+        self.memory_bus.write(0xFF04, 0);
+        // KEY1 reads as 0xFF on DMG, so check for CGB mode first
+        if self.memory_bus.cgb_mode() && self.memory_bus.read(0xFF4D) & 0x01 != 0 {
+            self.switch_speed();
+        } else {
+            self.stopped = true;
+        }
+    }
+
+    pub fn stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// A stopped CPU (and LCD) stay frozen, with no clock ticking at all,
+    /// until one of the joypad input lines P10-P13 goes low.
+    pub fn tick_stopped(&mut self) {
+        if self.memory_bus.read(0xFF00) & 0x0F != 0x0F {
+            self.stopped = false;
+        }
+    }
+
+    /// CGB speed switch, performed by STOP when KEY1 bit 0 is armed.
+    /// Bit 7 of KEY1 reports the current speed and the armed bit is cleared.
+    /// Does nothing outside CGB mode.
+    pub fn switch_speed(&mut self) {
+        if !self.memory_bus.cgb_mode() {
+            return;
+        }
+        self.double_speed = !self.double_speed;
+        self.memory_bus.set_double_speed(self.double_speed);
+    }
+
+    pub fn double_speed(&self) -> bool {
+        self.double_speed
+    }
+
+    /// Executing an illegal opcode locks the CPU up for good. Only a reset
+    /// gets it going again.
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    /// A locked CPU never fetches again, but the clock (and everything
+    /// driven by it) keeps running.
+    pub fn tick_locked(&mut self) {
+        self.inc_clock(1);
+    }
+
+    pub fn set_breakpoint(&mut self) {
+        self.breakpoint = true;
+    }
+
+    /// True when the last instruction run by `step` was the LD B, B
+    /// software breakpoint, which programs use to signal they are done.
+    pub fn breakpoint(&self) -> bool {
+        self.breakpoint
+    }
+
+    pub fn clock(&self) -> u64 {
+        self.clock
+    }
 }
 
 pub fn concat_bytes(hi: u8, lo: u8) -> u16 {
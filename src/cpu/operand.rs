@@ -0,0 +1,87 @@
+use std::fmt;
+
+use crate::cpu::error::EmulatorError;
+use crate::cpu::registers::Register;
+use crate::cpu::registers::Register::PC;
+use crate::cpu::value::Value;
+use crate::cpu::{MemoryLocation, CPU};
+
+/// Where an instruction takes a value from or puts one. Decoding only
+/// describes the operand, the CPU resolves it when executing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    // d8
+    Immediate8(u8),
+    // d16
+    Immediate16(u16),
+    // (BC), (DE), (HL). An 8 bit register points into the high page,
+    // so Indirect(C) is (FF00+C)
+    Indirect(Register),
+    // (a16)
+    Absolute(u16),
+    // (FF00+a8)
+    High(u8),
+}
+
+// RGBDS syntax
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Register(reg) => write!(f, "{}", reg),
+            Operand::Immediate8(val) => write!(f, "${:02X}", val),
+            Operand::Immediate16(val) => write!(f, "${:04X}", val),
+            Operand::Indirect(reg) => write!(f, "[{}]", reg),
+            Operand::Absolute(addr) => write!(f, "[${:04X}]", addr),
+            Operand::High(lo) => write!(f, "[$FF{:02X}]", lo),
+        }
+    }
+}
+
+impl CPU {
+    /// Resolves an operand to the register or memory address it names.
+    /// Immediates have nowhere to be written back to.
+    pub fn location(&self, operand: Operand) -> Result<MemoryLocation, EmulatorError> {
+        match operand {
+            Operand::Register(reg) => Ok(MemoryLocation::Register(reg)),
+            _ => match self.address(operand) {
+                Some(addr) => Ok(MemoryLocation::Pointer(addr)),
+                None => Err(EmulatorError::UnsupportedOperand {
+                    operand,
+                    pc: self.registers.get(PC).extract(),
+                }),
+            },
+        }
+    }
+
+    pub fn read_operand(&self, operand: Operand) -> Value {
+        match operand {
+            Operand::Register(reg) => self.registers.get(reg),
+            Operand::Immediate8(val) => Value::EightBit(val),
+            Operand::Immediate16(val) => Value::SixteenBit(val),
+            _ => match self.address(operand) {
+                Some(addr) => self.read(addr, false),
+                None => unreachable!("{:?} is not a memory operand", operand),
+            },
+        }
+    }
+
+    pub fn write_operand(&mut self, operand: Operand, data: Value) -> Result<(), EmulatorError> {
+        let location = self.location(operand)?;
+        self.write_location(location, data);
+        Ok(())
+    }
+
+    // The address a memory operand points to
+    fn address(&self, operand: Operand) -> Option<Value> {
+        match operand {
+            Operand::Indirect(reg) => match self.registers.get(reg) {
+                Value::EightBit(lo) => Some(Value::SixteenBit(0xFF00 | lo as u16)),
+                addr => Some(addr),
+            },
+            Operand::Absolute(addr) => Some(Value::SixteenBit(addr)),
+            Operand::High(lo) => Some(Value::SixteenBit(0xFF00 | lo as u16)),
+            Operand::Register(_) | Operand::Immediate8(_) | Operand::Immediate16(_) => None,
+        }
+    }
+}
@@ -1,8 +1,10 @@
+use std::fmt;
+
 use crate::cpu::flag::FlagRegister;
 use crate::cpu::value::Value;
 use crate::cpu::{concat_bytes, split_bytes};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Register {
     A,
     B,
@@ -39,6 +41,27 @@ impl Register {
     }
 }
 
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Register::A => "a",
+            Register::B => "b",
+            Register::C => "c",
+            Register::D => "d",
+            Register::E => "e",
+            Register::H => "h",
+            Register::L => "l",
+            Register::AF => "af",
+            Register::BC => "bc",
+            Register::DE => "de",
+            Register::HL => "hl",
+            Register::SP => "sp",
+            Register::PC => "pc",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Registers {
     a: u8,
@@ -0,0 +1,87 @@
+/// DIV, TIMA, TMA and TAC. Everything is driven off a 16-bit divider
+/// counting T-cycles, of which DIV is the top half.
+#[derive(Debug, Default)]
+pub struct Timer {
+    divider: u16,
+    tima: u8,
+    tma: u8,
+    tac: u8,
+    // TIMA overflowed this M-cycle. It reads 0 until TMA is loaded next
+    // cycle.
+    overflowed: bool,
+    // TMA was loaded this M-cycle, and TIMA writes lose to it
+    reloading: bool,
+}
+
+impl Timer {
+    /// Runs one M-cycle. Returns true when the timer interrupt should be
+    /// requested.
+    pub(crate) fn tick(&mut self) -> bool {
+        self.reloading = false;
+        let interrupt = self.overflowed;
+        if self.overflowed {
+            self.overflowed = false;
+            self.tima = self.tma;
+            self.reloading = true;
+        }
+
+        let signal = self.signal();
+        self.divider = self.divider.wrapping_add(4);
+        self.falling_edge(signal);
+        interrupt
+    }
+
+    pub(crate) fn read(&self, address: u16) -> u8 {
+        match address {
+            0xFF04 => (self.divider >> 8) as u8,
+            0xFF05 => self.tima,
+            0xFF06 => self.tma,
+            0xFF07 => self.tac,
+            _ => 0xFF,
+        }
+    }
+
+    pub(crate) fn write(&mut self, address: u16, data: u8) {
+        let signal = self.signal();
+        match address {
+            // Any write clears the whole divider
+            0xFF04 => self.divider = 0,
+            0xFF05 if self.reloading => {}
+            // Writing during the delay cancels the reload and the interrupt
+            0xFF05 => {
+                self.tima = data;
+                self.overflowed = false;
+            }
+            0xFF06 => {
+                self.tma = data;
+                if self.reloading {
+                    self.tima = data;
+                }
+            }
+            0xFF07 => self.tac = data & 0x07,
+            _ => {}
+        }
+        // Resetting DIV or changing TAC can drop the signal too, which
+        // counts like any other falling edge
+        self.falling_edge(signal);
+    }
+
+    // The divider bit TIMA counts on, ANDed with the enable bit
+    fn signal(&self) -> bool {
+        let bit = match self.tac & 0x03 {
+            0b00 => 9,
+            0b01 => 3,
+            0b10 => 5,
+            _ => 7,
+        };
+        self.tac & 0x04 != 0 && self.divider & (1 << bit) != 0
+    }
+
+    fn falling_edge(&mut self, before: bool) {
+        if before && !self.signal() {
+            let (tima, overflow) = self.tima.overflowing_add(1);
+            self.tima = tima;
+            self.overflowed |= overflow;
+        }
+    }
+}
@@ -251,7 +251,6 @@ impl Value {
             Value::SixteenBit(val) => {
                 val
             }
-            _ => panic!("Failed to extract value"),
         }
     }
 }
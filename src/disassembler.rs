@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::cpu::flag::Flag;
+use crate::cpu::instruction::RotateDirection::{Left, Right};
+use crate::cpu::instruction::{AdditionalInstruction, Condition, Instruction, InstructionLength};
+use crate::cpu::operand::Operand;
+use crate::cpu::registers::Register::{C, HL};
+
+// Execution starts at 0x0100, and interrupt handlers are only ever reached
+// through their vectors, so they are traced from there too.
+const ENTRY_POINTS: [u16; 6] = [0x0100, 0x0040, 0x0048, 0x0050, 0x0058, 0x0060];
+
+/// One disassembled instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub address: u16,
+    pub bytes: Vec<u8>,
+    pub instruction: Instruction,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes: Vec<String> = self.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+        write!(
+            f,
+            "{:04X}  {:<8}  {}",
+            self.address,
+            bytes.join(" "),
+            mnemonic(&self.instruction, self.address)
+        )
+    }
+}
+
+/// Disassembles every instruction reachable from the entry point and the
+/// interrupt vectors, sorted by address. Jumps into RAM, or computed ones
+/// like JP HL, can't be followed.
+pub fn disassemble(rom: &[u8]) -> Vec<Line> {
+    let mut lines = BTreeMap::new();
+    let mut pending: Vec<u16> = ENTRY_POINTS.to_vec();
+
+    while let Some(address) = pending.pop() {
+        // Only the fixed bank and bank 1 are mapped at a known address
+        if lines.contains_key(&address) || address >= 0x8000 || address as usize >= rom.len() {
+            continue;
+        }
+        let start = address as usize;
+        let (instruction, length) = Instruction::decode(&rom[start..]);
+        let end = (start + length.count() as usize).min(rom.len());
+
+        pending.extend(successors(&instruction, address, length));
+        lines.insert(
+            address,
+            Line {
+                address,
+                bytes: rom[start..end].to_vec(),
+                instruction,
+            },
+        );
+    }
+
+    lines.into_values().collect()
+}
+
+// Addresses execution can carry on at after the instruction at `address`
+fn successors(instruction: &Instruction, address: u16, length: InstructionLength) -> Vec<u16> {
+    let next = address.wrapping_add(length.count());
+    match instruction {
+        Instruction::Jr {
+            how_far, condition, ..
+        } => with_fallthrough(jr_target(address, *how_far), condition, next),
+        Instruction::Jp {
+            to: Operand::Immediate16(target),
+            condition,
+            ..
+        } => with_fallthrough(*target, condition, next),
+        Instruction::Jp { .. } => vec![],
+        Instruction::Call { to, .. } => vec![*to, next],
+        Instruction::Rst(bit) => vec![bit.to_u8() as u16 * 8, next],
+        Instruction::Ret(Condition::None) | Instruction::Reti | Instruction::Illegal(_) => {
+            vec![]
+        }
+        _ => vec![next],
+    }
+}
+
+fn with_fallthrough(target: u16, condition: &Condition, next: u16) -> Vec<u16> {
+    match condition {
+        Condition::None => vec![target],
+        _ => vec![target, next],
+    }
+}
+
+// Offsets are relative to the end of the two byte JR instruction
+fn jr_target(address: u16, how_far: i8) -> u16 {
+    address.wrapping_add(2).wrapping_add_signed(how_far as i16)
+}
+
+/// RGBDS style mnemonic for `instruction`. `address` is where it sits, so
+/// that relative jumps can be shown with their absolute target.
+pub fn mnemonic(instruction: &Instruction, address: u16) -> String {
+    match instruction {
+        Instruction::Load {
+            to,
+            what,
+            additional_instruction,
+            ..
+        } => {
+            let operand = |op: &Operand| match (op, additional_instruction) {
+                (Operand::Indirect(HL), AdditionalInstruction::Inc) => "[hl+]".to_string(),
+                (Operand::Indirect(HL), AdditionalInstruction::Dec) => "[hl-]".to_string(),
+                _ => op.to_string(),
+            };
+            let high = |op: &Operand| matches!(op, Operand::High(_) | Operand::Indirect(C));
+            let name = if high(to) || high(what) { "ldh" } else { "ld" };
+            format!("{} {}, {}", name, operand(to), operand(what))
+        }
+        Instruction::Add { to, what, .. } => format!("add {}, {}", to, what),
+        Instruction::Adc { to, what, .. } => format!("adc {}, {}", to, what),
+        Instruction::AddSp { to, offset, .. } => match to {
+            HL => format!("ld hl, sp{:+}", offset),
+            _ => format!("add sp, {}", offset),
+        },
+        Instruction::Sub { from, what, .. } => format!("sub {}, {}", from, what),
+        Instruction::Sbc { from, what, .. } => format!("sbc {}, {}", from, what),
+        Instruction::Inc { what, .. } => format!("inc {}", what),
+        Instruction::Dec { what, .. } => format!("dec {}", what),
+        Instruction::Rot {
+            what,
+            direction,
+            use_carry,
+            length,
+            ..
+        } => {
+            let name = match (direction, use_carry) {
+                (Left, false) => "rlc",
+                (Left, true) => "rl",
+                (Right, false) => "rrc",
+                (Right, true) => "rr",
+            };
+            match length {
+                // RLCA, RLA, RRCA and RRA
+                InstructionLength::One => format!("{}a", name),
+                _ => format!("{} {}", name, what),
+            }
+        }
+        Instruction::Shift {
+            what,
+            direction,
+            arithmetic,
+            ..
+        } => {
+            let name = match (direction, arithmetic) {
+                (Left, _) => "sla",
+                (Right, true) => "sra",
+                (Right, false) => "srl",
+            };
+            format!("{} {}", name, what)
+        }
+        Instruction::Jr {
+            how_far, condition, ..
+        } => jump(
+            "jr",
+            condition,
+            &format!("${:04X}", jr_target(address, *how_far)),
+        ),
+        Instruction::Jp { to, condition, .. } => jump("jp", condition, &to.to_string()),
+        Instruction::And { what, .. } => format!("and a, {}", what),
+        Instruction::Xor { what, .. } => format!("xor a, {}", what),
+        Instruction::Or { what, .. } => format!("or a, {}", what),
+        Instruction::Cp { what, .. } => format!("cp a, {}", what),
+        Instruction::Swap(what) => format!("swap {}", what),
+        Instruction::Bit { what, bit } => format!("bit {}, {}", bit.to_u8(), what),
+        Instruction::Res { what, bit } => format!("res {}, {}", bit.to_u8(), what),
+        Instruction::Set { what, bit } => format!("set {}, {}", bit.to_u8(), what),
+        Instruction::Daa => "daa".to_string(),
+        Instruction::Cpl => "cpl".to_string(),
+        Instruction::Scf => "scf".to_string(),
+        Instruction::Ccf => "ccf".to_string(),
+        Instruction::Ret(condition) => match condition {
+            Condition::None => "ret".to_string(),
+            _ => format!("ret {}", condition_name(condition)),
+        },
+        Instruction::Reti => "reti".to_string(),
+        Instruction::Pop(reg) => format!("pop {}", reg),
+        Instruction::Push(reg) => format!("push {}", reg),
+        Instruction::Call { to, condition } => jump("call", condition, &format!("${:04X}", to)),
+        Instruction::Rst(bit) => format!("rst ${:02X}", bit.to_u8() * 8),
+        Instruction::Ei => "ei".to_string(),
+        Instruction::Di => "di".to_string(),
+        Instruction::Halt => "halt".to_string(),
+        Instruction::Stop => "stop".to_string(),
+        // LD B, B
+        Instruction::Breakpoint => "ld b, b".to_string(),
+        Instruction::Illegal(code) => format!("db ${:02X}", code),
+        Instruction::Nop => "nop".to_string(),
+    }
+}
+
+fn jump(name: &str, condition: &Condition, target: &str) -> String {
+    match condition {
+        Condition::None => format!("{} {}", name, target),
+        _ => format!("{} {}, {}", name, condition_name(condition), target),
+    }
+}
+
+fn condition_name(condition: &Condition) -> &'static str {
+    match condition {
+        Condition::FlagOn(Flag::Z) => "z",
+        Condition::FlagOff(Flag::Z) => "nz",
+        Condition::FlagOn(Flag::C) => "c",
+        Condition::FlagOff(Flag::C) => "nc",
+        // Only Z and C are ever tested by jumps
+        _ => "",
+    }
+}
@@ -1 +1,4 @@
-pub mod cpu;
+pub mod cartridge;
+pub mod cpu;
+pub mod disassembler;
+pub mod ppu;
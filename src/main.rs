@@ -1,32 +1,198 @@
 use std::env;
 use std::fs::File;
 use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use crate::cpu::CPU;
-use crate::cpu::registers::Register::PC;
-use crate::cpu::value::Value;
-
-mod cpu;
+use yabge::cartridge::header::Header;
+use yabge::cartridge::save::SaveFile;
+use yabge::cpu::registers::Register::PC;
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+use yabge::disassembler::disassemble;
+use yabge::ppu::Renderer;
 
 fn main() {
     // Get the command-line arguments
     let args: Vec<String> = env::args().collect();
 
-    // Check if the user provided a ROM file path
-    if args.len() != 2 {
-        println!("Usage: {} <rom_file>", args[0]);
+    // Subcommands come first, so a bad argument count doesn't make them
+    // look like ROM file names
+    match args.get(1).map(String::as_str) {
+        Some("disasm") => match args.get(2) {
+            Some(rom_file_path) => disasm(rom_file_path, &args[3..]),
+            None => usage(&args[0]),
+        },
+        Some("info") => match &args[2..] {
+            [rom_file_path] => info(rom_file_path),
+            _ => usage(&args[0]),
+        },
+        Some(rom_file_path) if !rom_file_path.starts_with("--") => run(rom_file_path, &args[2..]),
+        _ => usage(&args[0]),
+    }
+}
+
+fn usage(program: &str) {
+    println!(
+        "Usage: {} <rom_file> [--host-clock] [--save-dir <dir>] [--fifo]",
+        program
+    );
+    println!("       {} info <rom_file>", program);
+    println!(
+        "       {} disasm <rom_file> [--start <addr>] [--end <addr>]",
+        program
+    );
+}
+
+fn run(rom_file_path: &str, options: &[String]) {
+    let mut host_clock = false;
+    let mut save_dir = None;
+    let mut renderer = Renderer::Scanline;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--host-clock" => host_clock = true,
+            "--fifo" => renderer = Renderer::Fifo,
+            "--save-dir" => match options.next() {
+                Some(dir) => save_dir = Some(PathBuf::from(dir)),
+                None => {
+                    println!("--save-dir needs a directory.");
+                    return;
+                }
+            },
+            _ => {
+                println!("Unknown option {}.", option);
+                return;
+            }
+        }
+    }
+
+    let rom_data = match read_rom(rom_file_path) {
+        Some(data) => data,
+        None => return,
+    };
+
+    let mut cpu = CPU::new(renderer);
+    if let Err(error) = cpu.load_cartridge(rom_data) {
+        println!("{}", error);
         return;
     }
+    // Keep the cartridge clock in step with the real one
+    if let Some(rtc) = cpu
+        .cartridge_mut()
+        .and_then(|cartridge| cartridge.rtc_mut())
+    {
+        rtc.set_host_clock(host_clock);
+    }
+
+    let mut save_file = SaveFile::for_rom(Path::new(rom_file_path), save_dir.as_deref());
+    if let Some(cartridge) = cpu.cartridge_mut() {
+        if let Err(error) = save_file.load(cartridge) {
+            println!("Failed to read {}: {}", save_file.path().display(), error);
+            return;
+        }
+    }
+
+    cpu.registers.set(PC, Value::SixteenBit(0x100));
+    // The boot ROM leaves the LCD on with the usual palette
+    cpu.write(Value::SixteenBit(0xFF40), Value::EightBit(0x91));
+    cpu.write(Value::SixteenBit(0xFF47), Value::EightBit(0xFC));
+
+    // Ctrl-C and SIGTERM just raise a flag, which the loop checks between
+    // instructions
+    let quit = Arc::new(AtomicBool::new(false));
+    let handler_quit = Arc::clone(&quit);
+    if let Err(error) = ctrlc::set_handler(move || handler_quit.store(true, Ordering::SeqCst)) {
+        println!("Failed to set the Ctrl-C handler: {}", error);
+    }
+
+    // Run until asked to quit, or until the CPU locks up, then flush the
+    // save below
+    while !quit.load(Ordering::SeqCst) && !cpu.locked() {
+        let cycles = match cpu.step() {
+            Ok(cycles) => cycles,
+            Err(error) => {
+                println!("{}", error);
+                break;
+            }
+        };
+        if let Some(cartridge) = cpu.cartridge_mut() {
+            if let Err(error) = save_file.tick(cartridge, cycles) {
+                println!("Failed to write {}: {}", save_file.path().display(), error);
+            }
+        }
+    }
+
+    if let Some(cartridge) = cpu.cartridge_mut() {
+        if let Err(error) = save_file.flush(cartridge) {
+            println!("Failed to write {}: {}", save_file.path().display(), error);
+        }
+    }
+}
 
-    // Extract the ROM file path from the command-line arguments
-    let rom_file_path = &args[1];
+fn disasm(rom_file_path: &str, options: &[String]) {
+    let mut start = 0x0000;
+    let mut end = 0xFFFF;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let bound = match option.as_str() {
+            "--start" => &mut start,
+            "--end" => &mut end,
+            _ => {
+                println!("Unknown option {}.", option);
+                return;
+            }
+        };
+        match options.next().and_then(|addr| parse_address(addr)) {
+            Some(addr) => *bound = addr,
+            None => {
+                println!("{} needs a hex address, like 0x0150.", option);
+                return;
+            }
+        }
+    }
 
+    let rom_data = match read_rom(rom_file_path) {
+        Some(data) => data,
+        None => return,
+    };
+
+    for line in disassemble(&rom_data) {
+        if (start..=end).contains(&line.address) {
+            println!("{}", line);
+        }
+    }
+}
+
+fn info(rom_file_path: &str) {
+    let rom_data = match read_rom(rom_file_path) {
+        Some(data) => data,
+        None => return,
+    };
+
+    match Header::parse(&rom_data) {
+        Ok(header) => println!("{}", header),
+        Err(error) => println!("{}", error),
+    }
+}
+
+// Accepts 0150, 0x0150 or $0150
+fn parse_address(addr: &str) -> Option<u16> {
+    let digits = addr
+        .strip_prefix("0x")
+        .or_else(|| addr.strip_prefix('$'))
+        .unwrap_or(addr);
+    u16::from_str_radix(digits, 16).ok()
+}
+
+fn read_rom(rom_file_path: &str) -> Option<Vec<u8>> {
     // Open the ROM file
     let mut rom_file = match File::open(rom_file_path) {
         Ok(file) => file,
         Err(_) => {
             println!("Failed to open ROM file.");
-            return;
+            return None;
         }
     };
 
@@ -34,29 +200,7 @@ fn main() {
     let mut rom_data = Vec::new();
     if rom_file.read_to_end(&mut rom_data).is_err() {
         println!("Failed to read ROM file.");
+        return None;
     }
-    
-
-
-    let mut cpu = CPU::default();
-
-    for (index, data) in rom_data.iter().enumerate() {
-        cpu.write(Value::SixteenBit(index as u16), Value::EightBit(*data))
-    }
-    
-    cpu.registers.set(PC, Value::SixteenBit(0x100));
-    let mut current_code = Value::EightBit(rom_data[0x100usize]);
-
-    while current_code != Value::EightBit(0xFD) {
-        if let Value::EightBit(code) = current_code {
-            let inst = cpu.lookup(code);
-            println!("{:?}", inst);
-            cpu.execute(inst);
-            current_code = cpu.read(cpu.registers.get(PC), false);
-        } else {
-            println!("hmmm");
-            break
-        }
-    }
-    
+    Some(rom_data)
 }
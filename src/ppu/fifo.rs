@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+
+use crate::ppu::frame::WIDTH;
+use crate::ppu::object::Object;
+use crate::ppu::{shade, tile_color, Ppu, BG_MAP, OBJ_ENABLE, WINDOW_ENABLE, WINDOW_MAP};
+
+// Dots the fetcher spends at the start of each line on a tile it then
+// throws away
+const WARM_UP_DOTS: u8 = 6;
+// Dots an object fetch holds everything else up for, once the background
+// fetcher has a tile ready
+const OBJECT_FETCH_DOTS: u8 = 6;
+
+// The background fetcher's steps. All but Push take two dots, and Push
+// waits for the FIFO to empty.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Step {
+    #[default]
+    Tile,
+    DataLow,
+    DataHigh,
+    Push,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ObjectPixel {
+    color: u8,
+    obp1: bool,
+    behind_background: bool,
+}
+
+/// Pixel transfer state for one line, plus the WY check which lasts the
+/// whole frame.
+#[derive(Debug, Default)]
+pub(crate) struct Fifo {
+    // Color indexes waiting to go out
+    background: VecDeque<u8>,
+    objects: VecDeque<ObjectPixel>,
+    step: Step,
+    step_dots: u8,
+    // Tiles fetched so far, from the left of the line or the window
+    fetch_x: u8,
+    tile: u8,
+    low: u8,
+    high: u8,
+    // Next pixel out on the LCD
+    x: u8,
+    // Pixels still to throw away before anything is shown
+    discard: u8,
+    warm_up: u8,
+    // Where the next object to fetch sits in the line's scanned objects
+    next_object: usize,
+    // An object being fetched, and the dots spent on it so far
+    fetching: Option<(Object, u8)>,
+    window: bool,
+    // The window only shows on lines after LY has matched WY this frame
+    pub(crate) wy_matched: bool,
+}
+
+impl Ppu {
+    pub(crate) fn start_fifo_line(&mut self) {
+        let wy_matched = self.fifo.wy_matched || self.ly == self.wy;
+        self.fifo = Fifo {
+            discard: self.scx % 8,
+            warm_up: WARM_UP_DOTS,
+            wy_matched,
+            ..Fifo::default()
+        };
+    }
+
+    pub(crate) fn end_fifo_line(&mut self) {
+        if self.fifo.window {
+            self.window_line += 1;
+        }
+    }
+
+    // One dot of pixel transfer. Returns true once the last pixel of the
+    // line is out.
+    pub(crate) fn fifo_dot(&mut self) -> bool {
+        if self.fifo.warm_up > 0 {
+            self.fifo.warm_up -= 1;
+            return false;
+        }
+
+        // Everything else waits while an object is fetched, first for the
+        // background fetcher to finish its tile
+        if let Some((object, dots)) = self.fifo.fetching {
+            if self.fifo.step != Step::Push {
+                self.fetch_step();
+                return false;
+            }
+            if dots + 1 < OBJECT_FETCH_DOTS {
+                self.fifo.fetching = Some((object, dots + 1));
+                return false;
+            }
+            self.merge_object(&object);
+            self.fifo.fetching = None;
+        }
+
+        self.fetch_step();
+        if self.fifo.background.is_empty() {
+            return false;
+        }
+
+        if self.fifo.discard == 0 && self.window_starts() {
+            // The fetcher starts over on the window straight away
+            self.fifo.window = true;
+            self.fifo.background.clear();
+            self.fifo.step = Step::Tile;
+            self.fifo.step_dots = 0;
+            self.fifo.fetch_x = 0;
+            // With WX under 7 the window's left edge is off screen
+            self.fifo.discard = 7u8.saturating_sub(self.wx);
+            self.fetch_step();
+            return false;
+        }
+
+        if self.fifo.discard == 0 && self.lcdc & OBJ_ENABLE != 0 {
+            if let Some(object) = self.objects.get(self.fifo.next_object).copied() {
+                if object.x as u16 <= self.fifo.x as u16 + 8 {
+                    self.fifo.next_object += 1;
+                    self.fifo.fetching = Some((object, 0));
+                    return false;
+                }
+            }
+        }
+
+        self.shift_pixel()
+    }
+
+    fn window_starts(&self) -> bool {
+        !self.fifo.window
+            && self.fifo.wy_matched
+            && self.lcdc & WINDOW_ENABLE != 0
+            && self.fifo.x as u16 + 7 >= self.wx as u16
+    }
+
+    fn fetch_step(&mut self) {
+        if self.fifo.step == Step::Push {
+            if !self.fifo.background.is_empty() {
+                return;
+            }
+            for x in 0..8 {
+                let color = tile_color(self.fifo.low, self.fifo.high, x);
+                self.fifo.background.push_back(color);
+            }
+            self.fifo.fetch_x = self.fifo.fetch_x.wrapping_add(1);
+            // Pushing takes no time of its own, the next tile starts now
+            self.fifo.step = Step::Tile;
+        }
+
+        self.fifo.step_dots += 1;
+        if self.fifo.step_dots < 2 {
+            return;
+        }
+        self.fifo.step_dots = 0;
+        // Registers are read as each step happens, so writes partway
+        // through a line take effect from the next tile
+        let (map, x, y) = if self.fifo.window {
+            let map = if self.lcdc & WINDOW_MAP != 0 {
+                0x9C00
+            } else {
+                0x9800
+            };
+            (map, self.fifo.fetch_x.wrapping_mul(8), self.window_line)
+        } else {
+            let map = if self.lcdc & BG_MAP != 0 {
+                0x9C00
+            } else {
+                0x9800
+            };
+            let x = (self.scx & 0xF8).wrapping_add(self.fifo.fetch_x.wrapping_mul(8));
+            (map, x, self.ly.wrapping_add(self.scy))
+        };
+        self.fifo.step = match self.fifo.step {
+            Step::Tile => {
+                self.fifo.tile = self.map_tile(map, x, y);
+                Step::DataLow
+            }
+            Step::DataLow => {
+                self.fifo.low = self.vram[self.tile_row(self.fifo.tile, y)];
+                Step::DataHigh
+            }
+            Step::DataHigh => {
+                self.fifo.high = self.vram[self.tile_row(self.fifo.tile, y) + 1];
+                Step::Push
+            }
+            Step::Push => Step::Push,
+        };
+    }
+
+    // Objects fetched earlier keep their pixels, so only transparent ones
+    // are replaced
+    fn merge_object(&mut self, object: &Object) {
+        let (low, high) = self.object_row(object, self.ly);
+        // Columns already left behind, for objects hanging off the left edge
+        let skip = (self.fifo.x as usize + 8).saturating_sub(object.x as usize);
+        for column in skip..8 {
+            let bit = if object.x_flip() { 7 - column } else { column };
+            let pixel = ObjectPixel {
+                color: tile_color(low, high, bit as u8),
+                obp1: object.obp1(),
+                behind_background: object.behind_background(),
+            };
+            match self.fifo.objects.get_mut(column - skip) {
+                Some(existing) if existing.color == 0 => *existing = pixel,
+                Some(_) => {}
+                None => self.fifo.objects.push_back(pixel),
+            }
+        }
+    }
+
+    fn shift_pixel(&mut self) -> bool {
+        let color = match self.fifo.background.pop_front() {
+            Some(color) => color,
+            None => return false,
+        };
+        if self.fifo.discard > 0 {
+            self.fifo.discard -= 1;
+            return false;
+        }
+
+        let color = self.background_color(color);
+        let object = self.fifo.objects.pop_front();
+        let pixel = match object {
+            Some(object)
+                if self.lcdc & OBJ_ENABLE != 0
+                    && object.color != 0
+                    && !(object.behind_background && color != 0) =>
+            {
+                let palette = if object.obp1 { self.obp1 } else { self.obp0 };
+                shade(palette, object.color)
+            }
+            _ => shade(self.bgp, color),
+        };
+        self.back
+            .set_pixel(self.fifo.x as usize, self.ly as usize, pixel);
+        self.fifo.x += 1;
+        self.fifo.x as usize == WIDTH
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+pub const WIDTH: usize = 160;
+pub const HEIGHT: usize = 144;
+
+/// A full picture, one DMG shade per pixel from 0 (white) to 3 (black).
+#[derive(Clone, PartialEq, Eq)]
+pub struct Frame {
+    pixels: [u8; WIDTH * HEIGHT],
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Frame {
+            pixels: [0; WIDTH * HEIGHT],
+        }
+    }
+}
+
+impl Frame {
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * WIDTH + x]
+    }
+
+    pub(crate) fn set_pixel(&mut self, x: usize, y: usize, shade: u8) {
+        self.pixels[y * WIDTH + x] = shade;
+    }
+
+    /// Row by row, top left first.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// A binary PGM, which most image tools open, for saving screenshots
+    /// and reference images.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut pgm = format!("P5\n{} {}\n3\n", WIDTH, HEIGHT).into_bytes();
+        // PGM has 0 as black
+        pgm.extend(self.pixels.iter().map(|shade| 3 - shade));
+        pgm
+    }
+
+    /// The reverse of `to_pgm`, for loading reference images. Gray levels
+    /// are scaled down to the four shades.
+    pub fn from_pgm(pgm: &[u8]) -> Option<Frame> {
+        let mut fields = Vec::new();
+        let mut start = 0;
+        // Magic, width, height and max value, each followed by one
+        // whitespace byte
+        while fields.len() < 4 {
+            let len = pgm[start..].iter().position(u8::is_ascii_whitespace)?;
+            fields.push(std::str::from_utf8(&pgm[start..start + len]).ok()?);
+            start += len + 1;
+        }
+        let max: u32 = fields[3].parse().ok()?;
+        if fields[0] != "P5"
+            || fields[1] != WIDTH.to_string()
+            || fields[2] != HEIGHT.to_string()
+            || max == 0
+            || max > 255
+            || pgm.len() - start != WIDTH * HEIGHT
+        {
+            return None;
+        }
+
+        let mut frame = Frame::default();
+        for (pixel, gray) in frame.pixels.iter_mut().zip(&pgm[start..]) {
+            *pixel = 3 - (((*gray as u32).min(max) * 3 + max / 2) / max) as u8;
+        }
+        Some(frame)
+    }
+}
+
+// One character per pixel, which makes for readable test failures
+impl fmt::Debug for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.pixels.chunks(WIDTH) {
+            let line: String = row
+                .iter()
+                .map(|shade| ['.', ':', '+', '#'][*shade as usize])
+                .collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
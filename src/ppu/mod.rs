@@ -0,0 +1,554 @@
+use crate::cpu::interrupt::Interrupt;
+use crate::ppu::fifo::Fifo;
+use crate::ppu::frame::{Frame, WIDTH};
+use crate::ppu::object::Object;
+
+mod fifo;
+pub mod frame;
+pub mod object;
+
+// Dots, which are T-cycles at normal speed
+const OAM_SCAN_DOTS: u16 = 80;
+// Pixel transfer takes at least this long, and longer with fine scroll,
+// the window and objects
+const TRANSFER_DOTS: u16 = 172;
+const MAX_TRANSFER_DOTS: u16 = 289;
+const LINE_DOTS: u16 = 456;
+const LINES: u8 = 154;
+
+// LCDC bits
+const BG_ENABLE: u8 = 1 << 0;
+const OBJ_ENABLE: u8 = 1 << 1;
+const OBJ_SIZE: u8 = 1 << 2;
+const BG_MAP: u8 = 1 << 3;
+const TILE_DATA: u8 = 1 << 4;
+const WINDOW_ENABLE: u8 = 1 << 5;
+const WINDOW_MAP: u8 = 1 << 6;
+const LCD_ENABLE: u8 = 1 << 7;
+
+// STAT interrupt selects
+const HBLANK_SELECT: u8 = 1 << 3;
+const VBLANK_SELECT: u8 = 1 << 4;
+const OAM_SCAN_SELECT: u8 = 1 << 5;
+const LYC_SELECT: u8 = 1 << 6;
+
+/// The STAT mode, in the order of the bits it shows up as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    HBlank,
+    VBlank,
+    OamScan,
+    Transfer,
+}
+
+impl Mode {
+    pub fn bits(&self) -> u8 {
+        match self {
+            Mode::HBlank => 0,
+            Mode::VBlank => 1,
+            Mode::OamScan => 2,
+            Mode::Transfer => 3,
+        }
+    }
+}
+
+/// How the PPU turns VRAM into pixels. Both draw into the same frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Renderer {
+    /// A whole line at once at the end of pixel transfer. Quick, but
+    /// registers written partway through a line don't show up.
+    #[default]
+    Scanline,
+    /// A dot at a time through the pixel FIFO, the way the hardware does
+    /// it, so mid-line writes land where they should.
+    Fifo,
+}
+
+/// The picture processing unit. Owns VRAM, OAM and the LCD registers, and
+/// draws each frame with the chosen `Renderer`: a whole line at once, or a
+/// pixel per dot through the FIFO.
+#[derive(Debug)]
+pub struct Ppu {
+    renderer: Renderer,
+    vram: [u8; 0x2000],
+    oam: [u8; 0xA0],
+    lcdc: u8,
+    // Only the interrupt select bits, the rest is worked out on read
+    stat: u8,
+    scy: u8,
+    scx: u8,
+    ly: u8,
+    lyc: u8,
+    bgp: u8,
+    obp0: u8,
+    obp1: u8,
+    wy: u8,
+    wx: u8,
+    mode: Mode,
+    // Position within the current line
+    dot: u16,
+    // Length of this line's pixel transfer
+    transfer_dots: u16,
+    // Objects picked up by this line's OAM scan
+    objects: Vec<Object>,
+    // The STAT interrupt only fires when this goes from low to high, so
+    // one condition staying true blocks the others
+    stat_line: bool,
+    // The window keeps its own line count, which only moves on lines it
+    // was drawn on
+    window_line: u8,
+    // Pixel transfer state for the FIFO renderer
+    fifo: Fifo,
+    // Drawn into during the frame, and copied out at VBlank
+    back: Frame,
+    front: Frame,
+    frame_count: u64,
+}
+
+impl Default for Ppu {
+    fn default() -> Self {
+        Ppu {
+            renderer: Renderer::default(),
+            vram: [0; 0x2000],
+            oam: [0; 0xA0],
+            lcdc: 0,
+            stat: 0,
+            scy: 0,
+            scx: 0,
+            ly: 0,
+            lyc: 0,
+            bgp: 0,
+            obp0: 0,
+            obp1: 0,
+            wy: 0,
+            wx: 0,
+            mode: Mode::HBlank,
+            dot: 0,
+            transfer_dots: TRANSFER_DOTS,
+            objects: Vec::new(),
+            stat_line: false,
+            window_line: 0,
+            fifo: Fifo::default(),
+            back: Frame::default(),
+            front: Frame::default(),
+            frame_count: 0,
+        }
+    }
+}
+
+impl Ppu {
+    pub fn new(renderer: Renderer) -> Ppu {
+        Ppu {
+            renderer,
+            ..Ppu::default()
+        }
+    }
+
+    pub fn renderer(&self) -> Renderer {
+        self.renderer
+    }
+
+    /// The last finished frame.
+    pub fn frame(&self) -> &Frame {
+        &self.front
+    }
+
+    /// Frames finished since power on. Goes up at the start of each VBlank.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Runs the PPU for some dots. Returns the IF bits of any interrupts
+    /// it requested along the way.
+    pub(crate) fn tick(&mut self, dots: u8) -> u8 {
+        if self.lcdc & LCD_ENABLE == 0 {
+            return 0;
+        }
+        let mut interrupts = 0;
+        for _ in 0..dots {
+            interrupts |= self.tick_dot();
+        }
+        interrupts
+    }
+
+    fn tick_dot(&mut self) -> u8 {
+        self.dot += 1;
+        if self.dot == LINE_DOTS {
+            self.dot = 0;
+            self.ly = (self.ly + 1) % LINES;
+            if self.ly == 0 {
+                self.window_line = 0;
+            }
+        }
+
+        let mode = match (self.ly, self.dot) {
+            (144.., _) => Mode::VBlank,
+            (_, dot) if dot < OAM_SCAN_DOTS => Mode::OamScan,
+            (_, dot) if dot < OAM_SCAN_DOTS + self.transfer_dots => Mode::Transfer,
+            _ => Mode::HBlank,
+        };
+        let mut interrupts = 0;
+        if mode != self.mode {
+            interrupts |= self.enter(mode);
+        }
+        // The FIFO decides for itself when the line is done
+        if self.mode == Mode::Transfer && self.renderer == Renderer::Fifo && self.fifo_dot() {
+            self.transfer_dots = self.dot + 1 - OAM_SCAN_DOTS;
+        }
+        interrupts | self.update_stat_line()
+    }
+
+    fn enter(&mut self, mode: Mode) -> u8 {
+        self.mode = mode;
+        match mode {
+            Mode::Transfer => {
+                self.objects = if self.lcdc & OBJ_ENABLE != 0 {
+                    object::scan(&self.oam, self.ly, self.object_height())
+                } else {
+                    vec![]
+                };
+                self.transfer_dots = match self.renderer {
+                    Renderer::Scanline => self.transfer_length(),
+                    Renderer::Fifo => {
+                        self.start_fifo_line();
+                        LINE_DOTS - OAM_SCAN_DOTS
+                    }
+                };
+                0
+            }
+            Mode::HBlank => {
+                match self.renderer {
+                    // The whole line goes out at the end of pixel transfer
+                    Renderer::Scanline => self.render_line(),
+                    Renderer::Fifo => self.end_fifo_line(),
+                }
+                0
+            }
+            Mode::VBlank => {
+                self.fifo.wy_matched = false;
+                self.front = self.back.clone();
+                self.frame_count += 1;
+                Interrupt::VBlank.bit()
+            }
+            Mode::OamScan => 0,
+        }
+    }
+
+    // How long pixel transfer runs on this line, following the Pan Docs
+    // breakdown of the mode 3 penalties
+    fn transfer_length(&self) -> u16 {
+        // Pixels scrolled off the left of the first tile are fetched and
+        // thrown away
+        let mut dots = TRANSFER_DOTS + (self.scx % 8) as u16;
+        if self.window_on_line() {
+            dots += 6;
+        }
+        let mut tiles = vec![];
+        for object in &self.objects {
+            if object.x >= 168 {
+                continue;
+            }
+            dots += 6;
+            if object.x == 0 {
+                dots += 5;
+                continue;
+            }
+            // The fetcher has to wait for the background tile under the
+            // object, unless it already did for another one
+            let position = object.x.wrapping_add(self.scx);
+            if !tiles.contains(&(position / 8)) {
+                tiles.push(position / 8);
+                dots += 5u16.saturating_sub((position % 8) as u16);
+            }
+        }
+        dots.min(MAX_TRANSFER_DOTS)
+    }
+
+    fn window_on_line(&self) -> bool {
+        self.lcdc & WINDOW_ENABLE != 0 && self.wx <= 166 && self.ly >= self.wy
+    }
+
+    // Returns the STAT interrupt bit on a rising edge
+    fn update_stat_line(&mut self) -> u8 {
+        let select = match self.mode {
+            Mode::HBlank => HBLANK_SELECT,
+            Mode::VBlank => VBLANK_SELECT,
+            Mode::OamScan => OAM_SCAN_SELECT,
+            Mode::Transfer => 0,
+        };
+        let line = self.lcdc & LCD_ENABLE != 0
+            && (self.stat & select != 0 || self.stat & LYC_SELECT != 0 && self.ly == self.lyc);
+        let rising = line && !self.stat_line;
+        self.stat_line = line;
+        if rising {
+            Interrupt::LcdStat.bit()
+        } else {
+            0
+        }
+    }
+
+    fn render_line(&mut self) {
+        let y = self.ly;
+        let window = self.window_on_line();
+        let mut window_drawn = false;
+        let objects = std::mem::take(&mut self.objects);
+
+        for x in 0..WIDTH as u8 {
+            let color = if window && x as u16 + 7 >= self.wx as u16 {
+                window_drawn = true;
+                let map = if self.lcdc & WINDOW_MAP != 0 {
+                    0x9C00
+                } else {
+                    0x9800
+                };
+                self.tile_pixel(map, x + 7 - self.wx, self.window_line)
+            } else {
+                let map = if self.lcdc & BG_MAP != 0 {
+                    0x9C00
+                } else {
+                    0x9800
+                };
+                self.tile_pixel(map, x.wrapping_add(self.scx), y.wrapping_add(self.scy))
+            };
+            let color = self.background_color(color);
+
+            let pixel = match self.object_pixel(&objects, x, y) {
+                Some((object, _)) if object.behind_background() && color != 0 => {
+                    shade(self.bgp, color)
+                }
+                Some((object, object_color)) => {
+                    let palette = if object.obp1() { self.obp1 } else { self.obp0 };
+                    shade(palette, object_color)
+                }
+                None => shade(self.bgp, color),
+            };
+            self.back.set_pixel(x as usize, y as usize, pixel);
+        }
+
+        if window_drawn {
+            self.window_line += 1;
+        }
+    }
+
+    // The color index a background or window pixel ends up with. Both
+    // renderers fetch as usual and blank it here: on DMG, clearing LCDC bit 0
+    // blanks the window too.
+    fn background_color(&self, color: u8) -> u8 {
+        if self.lcdc & BG_ENABLE == 0 {
+            0
+        } else {
+            color
+        }
+    }
+
+    fn object_height(&self) -> u8 {
+        if self.lcdc & OBJ_SIZE != 0 {
+            16
+        } else {
+            8
+        }
+    }
+
+    // The first object in drawing order with a non-transparent pixel at x,
+    // along with that pixel's color index
+    fn object_pixel(&self, objects: &[Object], x: u8, y: u8) -> Option<(Object, u8)> {
+        objects.iter().find_map(|object| {
+            let column = x as i16 + 8 - object.x as i16;
+            if !(0..8).contains(&column) {
+                return None;
+            }
+            let column = if object.x_flip() { 7 - column } else { column };
+            let (low, high) = self.object_row(object, y);
+            let color = tile_color(low, high, column as u8);
+            if color == 0 {
+                None
+            } else {
+                Some((*object, color))
+            }
+        })
+    }
+
+    // The two bytes of an object's tile row that line y goes through
+    fn object_row(&self, object: &Object, y: u8) -> (u8, u8) {
+        let height = self.object_height();
+        // LCDC.2 can change after the OAM scan picked the object up, so only
+        // the bits of the row the current height has room for count
+        let mut row = y.wrapping_add(16).wrapping_sub(object.y) & (height - 1);
+        if object.y_flip() {
+            row = height - 1 - row;
+        }
+        // 8x16 objects ignore the low bit of the tile number
+        let tile = if height == 16 {
+            object.tile & 0xFE
+        } else {
+            object.tile
+        };
+        // Objects always use the 0x8000 tile data
+        let address = tile as usize * 16 + row as usize * 2;
+        (self.vram[address], self.vram[address + 1])
+    }
+
+    // Color index 0-3 at a position in a 256x256 tile map
+    fn tile_pixel(&self, map: u16, x: u8, y: u8) -> u8 {
+        let row = self.tile_row(self.map_tile(map, x, y), y);
+        tile_color(self.vram[row], self.vram[row + 1], x % 8)
+    }
+
+    // The tile number a tile map holds for a position
+    fn map_tile(&self, map: u16, x: u8, y: u8) -> u8 {
+        let index = map + (y as u16 / 8) * 32 + x as u16 / 8;
+        self.vram[index as usize - 0x8000]
+    }
+
+    // Where in VRAM the low byte of a background or window tile row is
+    fn tile_row(&self, tile: u8, y: u8) -> usize {
+        // 0x8000 counts up from tile 0, 0x8800 has tile 0 at 0x9000
+        let address = if self.lcdc & TILE_DATA != 0 {
+            0x8000 + tile as u16 * 16
+        } else {
+            (0x9000 + tile as i8 as i32 * 16) as u16
+        };
+        address as usize - 0x8000 + (y as usize % 8) * 2
+    }
+
+    pub(crate) fn read_vram(&self, address: u16) -> u8 {
+        self.vram[address as usize - 0x8000]
+    }
+
+    pub(crate) fn write_vram(&mut self, address: u16, data: u8) {
+        self.vram[address as usize - 0x8000] = data;
+    }
+
+    pub(crate) fn read_oam(&self, address: u16) -> u8 {
+        self.oam[address as usize - 0xFE00]
+    }
+
+    pub(crate) fn write_oam(&mut self, address: u16, data: u8) {
+        self.oam[address as usize - 0xFE00] = data;
+    }
+
+    pub(crate) fn read_register(&self, address: u16) -> u8 {
+        match address {
+            0xFF40 => self.lcdc,
+            0xFF41 => {
+                let coincidence = if self.ly == self.lyc { 0x04 } else { 0x00 };
+                self.stat | coincidence | self.mode.bits()
+            }
+            0xFF42 => self.scy,
+            0xFF43 => self.scx,
+            0xFF44 => self.ly,
+            0xFF45 => self.lyc,
+            0xFF47 => self.bgp,
+            0xFF48 => self.obp0,
+            0xFF49 => self.obp1,
+            0xFF4A => self.wy,
+            0xFF4B => self.wx,
+            _ => 0xFF,
+        }
+    }
+
+    /// Returns the IF bits of any interrupt the write set off.
+    pub(crate) fn write_register(&mut self, address: u16, data: u8) -> u8 {
+        match address {
+            0xFF40 => {
+                // Turning the LCD off resets it to the top of the frame
+                if data & LCD_ENABLE == 0 {
+                    self.ly = 0;
+                    self.dot = 0;
+                    self.mode = Mode::HBlank;
+                    self.window_line = 0;
+                    self.fifo.wy_matched = false;
+                } else if self.lcdc & LCD_ENABLE == 0 {
+                    self.mode = Mode::OamScan;
+                }
+                self.lcdc = data;
+            }
+            0xFF41 => self.stat = data & 0x78,
+            0xFF42 => self.scy = data,
+            0xFF43 => self.scx = data,
+            0xFF45 => self.lyc = data,
+            0xFF47 => self.bgp = data,
+            0xFF48 => self.obp0 = data,
+            0xFF49 => self.obp1 = data,
+            0xFF4A => self.wy = data,
+            0xFF4B => self.wx = data,
+            _ => {}
+        }
+        // Changing the selects or LYC can raise the line straight away
+        self.update_stat_line()
+    }
+}
+
+// Bit x of a tile row, split over two bytes with the low bit first
+fn tile_color(low: u8, high: u8, x: u8) -> u8 {
+    let bit = 7 - x;
+    (high >> bit & 1) << 1 | (low >> bit & 1)
+}
+
+// Looks a color index up in BGP, OBP0 or OBP1
+fn shade(palette: u8, color: u8) -> u8 {
+    palette >> (color * 2) & 0x03
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ppu::object::Object;
+    use crate::ppu::{shade, tile_color, Ppu, WINDOW_ENABLE};
+
+    fn object(x: u8, index: u8) -> Object {
+        Object {
+            y: 16,
+            x,
+            tile: 0,
+            flags: 0,
+            index,
+        }
+    }
+
+    #[test]
+    fn test_transfer_length() {
+        let mut ppu = Ppu::default();
+        assert_eq!(ppu.transfer_length(), 172);
+        ppu.scx = 3;
+        assert_eq!(ppu.transfer_length(), 175);
+        ppu.lcdc = WINDOW_ENABLE;
+        assert_eq!(ppu.transfer_length(), 181);
+
+        // 6 dots each, plus a wait on the background tile for the first
+        // object in it, which shrinks the further right the object sits
+        ppu.lcdc = 0;
+        ppu.scx = 0;
+        ppu.objects = vec![object(8, 0), object(10, 1), object(21, 2)];
+        assert_eq!(ppu.transfer_length(), 172 + 11 + 6 + 6);
+        ppu.objects = vec![object(0, 0), object(168, 1)];
+        assert_eq!(ppu.transfer_length(), 172 + 11);
+
+        // Ten objects at X 0 would push it over the limit
+        ppu.scx = 7;
+        ppu.objects = (0..10).map(|index| object(0, index)).collect();
+        assert_eq!(ppu.transfer_length(), 289);
+    }
+
+    #[test]
+    fn test_tile_color() {
+        // The first row of the Pan Docs example tile, 0x3C 0x7E
+        let colors: Vec<u8> = (0..8).map(|x| tile_color(0x3C, 0x7E, x)).collect();
+        assert_eq!(colors, [0, 2, 3, 3, 3, 3, 2, 0]);
+    }
+
+    #[test]
+    fn test_shade() {
+        assert_eq!(
+            (0..4).map(|color| shade(0xE4, color)).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(
+            (0..4).map(|color| shade(0x1B, color)).collect::<Vec<_>>(),
+            [3, 2, 1, 0]
+        );
+    }
+}
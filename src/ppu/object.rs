@@ -0,0 +1,92 @@
+/// Most objects the PPU picks up on one line.
+pub const MAX_PER_LINE: usize = 10;
+
+/// One of the 40 entries in OAM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Object {
+    // Screen position plus 16 and 8, so 0 hides the object
+    pub y: u8,
+    pub x: u8,
+    pub tile: u8,
+    pub flags: u8,
+    // Place in OAM, the tie-breaker between objects at the same X
+    pub index: u8,
+}
+
+impl Object {
+    pub fn from_oam(oam: &[u8], index: usize) -> Object {
+        let entry = &oam[index * 4..index * 4 + 4];
+        Object {
+            y: entry[0],
+            x: entry[1],
+            tile: entry[2],
+            flags: entry[3],
+            index: index as u8,
+        }
+    }
+
+    /// Drawn under background colors 1-3.
+    pub fn behind_background(&self) -> bool {
+        self.flags & 0x80 != 0
+    }
+
+    pub fn y_flip(&self) -> bool {
+        self.flags & 0x40 != 0
+    }
+
+    pub fn x_flip(&self) -> bool {
+        self.flags & 0x20 != 0
+    }
+
+    /// OBP1 rather than OBP0.
+    pub fn obp1(&self) -> bool {
+        self.flags & 0x10 != 0
+    }
+
+    pub fn on_line(&self, ly: u8, height: u8) -> bool {
+        let top = self.y as i16 - 16;
+        (top..top + height as i16).contains(&(ly as i16))
+    }
+}
+
+/// The objects on line `ly`, in the order they're drawn over each other.
+/// Only the first ten in OAM count, even if some of them are off screen.
+pub fn scan(oam: &[u8], ly: u8, height: u8) -> Vec<Object> {
+    let mut objects: Vec<Object> = (0..40)
+        .map(|index| Object::from_oam(oam, index))
+        .filter(|object| object.on_line(ly, height))
+        .take(MAX_PER_LINE)
+        .collect();
+    // On DMG the leftmost object wins, then the first in OAM
+    objects.sort_by_key(|object| (object.x, object.index));
+    objects
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ppu::object::scan;
+
+    #[test]
+    fn test_scan_order_and_limit() {
+        let mut oam = [0; 0xA0];
+        for index in 0..12 {
+            oam[index * 4] = 16;
+            oam[index * 4 + 1] = 100 - index as u8;
+        }
+        let objects = scan(&oam, 0, 8);
+        assert_eq!(objects.len(), 10);
+        assert_eq!(objects[0].index, 9);
+        assert_eq!(objects[9].index, 0);
+    }
+
+    #[test]
+    fn test_scan_height() {
+        let mut oam = [0; 0xA0];
+        oam[0] = 16 + 10;
+        assert!(scan(&oam, 9, 16).is_empty());
+        assert_eq!(scan(&oam, 17, 8).len(), 1);
+        assert!(scan(&oam, 18, 8).is_empty());
+        assert_eq!(scan(&oam, 25, 16).len(), 1);
+        assert!(scan(&oam, 26, 16).is_empty());
+    }
+}
@@ -0,0 +1,559 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use yabge::cartridge::error::CartridgeError;
+use yabge::cartridge::header::{
+    global_checksum, header_checksum, CartridgeType, CgbSupport, Header, Licensee, Mbc,
+};
+use yabge::cartridge::save::SaveFile;
+use yabge::cartridge::Cartridge;
+use yabge::cpu::registers::Register::PC;
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+mod common;
+
+use common::{read, write};
+
+// A 32KB ROM with the given header bytes and valid checksums
+fn rom_with_header(title: &[u8], cartridge_type: u8, rom_size: u8, ram_size: u8) -> Vec<u8> {
+    let mut rom = vec![0x00; 0x8000];
+    rom[0x0134..0x0134 + title.len()].copy_from_slice(title);
+    rom[0x0147] = cartridge_type;
+    rom[0x0148] = rom_size;
+    rom[0x0149] = ram_size;
+    fix_checksums(&mut rom);
+    rom
+}
+
+fn fix_checksums(rom: &mut [u8]) {
+    rom[0x014D] = header_checksum(rom);
+    let global = global_checksum(rom);
+    rom[0x014E] = (global >> 8) as u8;
+    rom[0x014F] = global as u8;
+}
+
+#[test]
+fn test_parse_header() {
+    let mut rom = rom_with_header(b"TETRIS", 0x03, 0x01, 0x02);
+    rom[0x014B] = 0x01;
+    rom[0x014C] = 0x01;
+    fix_checksums(&mut rom);
+
+    let header = Header::parse(&rom).unwrap();
+    assert_eq!(header.title, "TETRIS");
+    assert_eq!(header.manufacturer, None);
+    assert_eq!(header.cgb, CgbSupport::None);
+    assert!(!header.sgb);
+    assert_eq!(header.cartridge_type.code, 0x03);
+    assert_eq!(header.cartridge_type.mbc, Mbc::Mbc1);
+    assert!(header.cartridge_type.ram);
+    assert!(header.cartridge_type.battery);
+    assert!(!header.cartridge_type.timer);
+    assert_eq!(header.rom_size(), Some(0x10000));
+    assert_eq!(header.ram_size(), Some(0x2000));
+    assert_eq!(header.licensee, Licensee::Old(0x01));
+    assert_eq!(header.version, 1);
+    assert!(header.header_checksum.is_valid());
+    assert!(header.global_checksum.is_valid());
+}
+
+#[test]
+fn test_parse_cgb_header() {
+    let mut rom = rom_with_header(b"POKEMON_GLDAAUE", 0x10, 0x06, 0x03);
+    rom[0x0143] = 0x80;
+    rom[0x0144] = b'0';
+    rom[0x0145] = b'1';
+    rom[0x0146] = 0x03;
+    rom[0x014B] = 0x33;
+    fix_checksums(&mut rom);
+
+    let header = Header::parse(&rom).unwrap();
+    assert_eq!(header.title, "POKEMON_GLD");
+    assert_eq!(header.manufacturer, Some("AAUE".to_string()));
+    assert_eq!(header.cgb, CgbSupport::Enhanced);
+    assert!(header.sgb);
+    assert_eq!(header.cartridge_type.mbc, Mbc::Mbc3);
+    assert!(header.cartridge_type.timer);
+    assert_eq!(header.rom_size(), Some(0x200000));
+    assert_eq!(header.ram_size(), Some(0x8000));
+    assert_eq!(header.licensee, Licensee::New("01".to_string()));
+}
+
+#[test]
+fn test_cgb_only() {
+    let mut rom = rom_with_header(b"", 0x00, 0x00, 0x00);
+    rom[0x0143] = 0xC0;
+    fix_checksums(&mut rom);
+
+    assert_eq!(Header::parse(&rom).unwrap().cgb, CgbSupport::Only);
+}
+
+#[test]
+fn test_bad_checksums() {
+    let mut rom = rom_with_header(b"CORRUPT", 0x00, 0x00, 0x00);
+    // Flip a header byte after the checksums were computed
+    rom[0x0134] = b'X';
+
+    let header = Header::parse(&rom).unwrap();
+    assert!(!header.header_checksum.is_valid());
+    assert!(!header.global_checksum.is_valid());
+
+    // Code outside the header only breaks the global checksum
+    let mut rom = rom_with_header(b"CORRUPT", 0x00, 0x00, 0x00);
+    rom[0x0150] = 0xAA;
+
+    let header = Header::parse(&rom).unwrap();
+    assert!(header.header_checksum.is_valid());
+    assert!(!header.global_checksum.is_valid());
+    assert_eq!(
+        header.global_checksum.computed,
+        header.global_checksum.expected + 0xAA
+    );
+}
+
+#[test]
+fn test_header_checksum() {
+    let rom = rom_with_header(b"TETRIS", 0x00, 0x00, 0x00);
+    // 0 - sum of every byte - one per byte, over 0x0134-0x014C
+    let sum: u32 = b"TETRIS".iter().map(|b| *b as u32).sum::<u32>() + 25;
+    assert_eq!(rom[0x014D], (0u32.wrapping_sub(sum) & 0xFF) as u8);
+}
+
+#[test]
+fn test_missing_header() {
+    let rom = vec![0x00; 0x0100];
+    assert_eq!(
+        Header::parse(&rom),
+        Err(CartridgeError::MissingHeader { len: 0x0100 })
+    );
+}
+
+#[test]
+fn test_unknown_sizes() {
+    let rom = rom_with_header(b"", 0x00, 0x52, 0x07);
+    let header = Header::parse(&rom).unwrap();
+    assert_eq!(header.rom_size(), None);
+    assert_eq!(header.ram_size(), None);
+}
+
+#[test]
+fn test_load_cartridge() {
+    let mut rom = rom_with_header(b"", 0x00, 0x00, 0x00);
+    rom[0x0000] = 0x12;
+    rom[0x4000] = 0x34;
+    rom[0x7FFF] = 0x56;
+    fix_checksums(&mut rom);
+
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(rom).unwrap();
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0x0000), false),
+        Value::EightBit(0x12)
+    );
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0x4000), false),
+        Value::EightBit(0x34)
+    );
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0x7FFF), false),
+        Value::EightBit(0x56)
+    );
+    assert_eq!(cpu.cartridge().unwrap().header().cartridge_type.code, 0x00);
+}
+
+#[test]
+fn test_rom_is_read_only() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(rom_with_header(b"", 0x00, 0x00, 0x00))
+        .unwrap();
+
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0xAB));
+    cpu.write(Value::SixteenBit(0x4000), Value::EightBit(0xAB));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0x0000), false),
+        Value::EightBit(0x00)
+    );
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0x4000), false),
+        Value::EightBit(0x00)
+    );
+}
+
+#[test]
+fn test_external_ram() {
+    // No RAM on the cartridge, so the bus floats high
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(rom_with_header(b"", 0x00, 0x00, 0x00))
+        .unwrap();
+    cpu.write(Value::SixteenBit(0xA000), Value::EightBit(0x42));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xA000), false),
+        Value::EightBit(0xFF)
+    );
+
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(rom_with_header(b"", 0x08, 0x00, 0x02))
+        .unwrap();
+    cpu.write(Value::SixteenBit(0xBFFF), Value::EightBit(0x42));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xBFFF), false),
+        Value::EightBit(0x42)
+    );
+}
+
+#[test]
+fn test_truncated_rom() {
+    let mut rom = rom_with_header(b"", 0x00, 0x00, 0x00);
+    rom.truncate(0x6000);
+    assert_eq!(
+        Cartridge::new(rom).unwrap_err(),
+        CartridgeError::Truncated {
+            expected: 0x8000,
+            actual: 0x6000
+        }
+    );
+
+    let mut cpu = CPU::default();
+    assert_eq!(
+        cpu.load_cartridge(vec![0x00; 0x0100]),
+        Err(CartridgeError::MissingHeader { len: 0x0100 })
+    );
+}
+
+#[test]
+fn test_oversized_rom() {
+    let mut rom = rom_with_header(b"", 0x00, 0x00, 0x00);
+    rom.resize(0x10000, 0x00);
+    assert_eq!(
+        Cartridge::new(rom).unwrap_err(),
+        CartridgeError::Oversized {
+            expected: 0x8000,
+            actual: 0x10000
+        }
+    );
+}
+
+#[test]
+fn test_rejected_headers() {
+    let mut rom = rom_with_header(b"", 0x00, 0x00, 0x00);
+    rom[0x014D] = rom[0x014D].wrapping_add(1);
+    assert!(matches!(
+        Cartridge::new(rom),
+        Err(CartridgeError::BadHeaderChecksum { .. })
+    ));
+
+    let rom = rom_with_header(b"", 0x00, 0x52, 0x00);
+    assert_eq!(
+        Cartridge::new(rom).unwrap_err(),
+        CartridgeError::UnknownRomSize { code: 0x52 }
+    );
+
+    let rom = rom_with_header(b"", 0x20, 0x00, 0x00);
+    assert_eq!(
+        Cartridge::new(rom).unwrap_err(),
+        CartridgeError::UnsupportedCartridgeType(CartridgeType::from_code(0x20))
+    );
+}
+
+// A ROM of the given size code, with each bank starting with its own number
+fn banked_rom(cartridge_type: u8, rom_size: u8, ram_size: u8) -> Vec<u8> {
+    let mut rom = rom_with_header(b"", cartridge_type, rom_size, ram_size);
+    rom.resize(0x8000 << rom_size, 0x00);
+    for bank in 1..rom.len() / 0x4000 {
+        rom[bank * 0x4000] = bank as u8;
+    }
+    fix_checksums(&mut rom);
+    rom
+}
+
+// Spins on JP 0xC000 for a while, so the clock has something to count
+fn run_seconds(cpu: &mut CPU, seconds: u64) {
+    write(cpu, 0xC000, 0xC3);
+    write(cpu, 0xC001, 0x00);
+    write(cpu, 0xC002, 0xC0);
+    cpu.registers.set(PC, Value::SixteenBit(0xC000));
+    cpu.run_for_cycles(seconds * 0x100000).unwrap();
+}
+
+#[test]
+fn test_mbc1_rom_banks() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x01, 0x04, 0x00)).unwrap();
+    assert_eq!(read(&cpu, 0x4000), 1);
+
+    write(&mut cpu, 0x2000, 0x05);
+    assert_eq!(read(&cpu, 0x4000), 5);
+    assert_eq!(read(&cpu, 0x0000), 0);
+
+    // Bank 0 turns into bank 1
+    write(&mut cpu, 0x2000, 0x00);
+    assert_eq!(read(&cpu, 0x4000), 1);
+
+    // Only the low five bits are used
+    write(&mut cpu, 0x2000, 0x1F);
+    assert_eq!(read(&cpu, 0x4000), 31);
+    write(&mut cpu, 0x3FFF, 0xE3);
+    assert_eq!(read(&cpu, 0x4000), 3);
+}
+
+#[test]
+fn test_mbc1_large_rom() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x01, 0x06, 0x00)).unwrap();
+
+    write(&mut cpu, 0x2000, 0x02);
+    write(&mut cpu, 0x4000, 0x03);
+    assert_eq!(read(&cpu, 0x4000), 0x62);
+    assert_eq!(read(&cpu, 0x0000), 0x00);
+
+    // 0x20, 0x40 and 0x60 can't be reached through 0x4000-0x7FFF
+    write(&mut cpu, 0x2000, 0x00);
+    assert_eq!(read(&cpu, 0x4000), 0x61);
+
+    // Mode 1 puts the upper bits on 0x0000-0x3FFF too, which is the only
+    // way to get to those banks
+    write(&mut cpu, 0x6000, 0x01);
+    assert_eq!(read(&cpu, 0x0000), 0x60);
+    write(&mut cpu, 0x6000, 0x00);
+    assert_eq!(read(&cpu, 0x0000), 0x00);
+}
+
+#[test]
+fn test_mbc1_ram() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x03, 0x01, 0x03)).unwrap();
+
+    // Disabled until enabled
+    write(&mut cpu, 0xA000, 0x11);
+    assert_eq!(read(&cpu, 0xA000), 0xFF);
+
+    write(&mut cpu, 0x0000, 0x0A);
+    write(&mut cpu, 0xA000, 0x11);
+    assert_eq!(read(&cpu, 0xA000), 0x11);
+
+    // Mode 0 is stuck on RAM bank 0
+    write(&mut cpu, 0x4000, 0x02);
+    assert_eq!(read(&cpu, 0xA000), 0x11);
+
+    write(&mut cpu, 0x6000, 0x01);
+    assert_eq!(read(&cpu, 0xA000), 0x00);
+    write(&mut cpu, 0xA000, 0x22);
+    write(&mut cpu, 0x4000, 0x00);
+    assert_eq!(read(&cpu, 0xA000), 0x11);
+    write(&mut cpu, 0x4000, 0x02);
+    assert_eq!(read(&cpu, 0xA000), 0x22);
+
+    // Anything without 0xA in the low nibble disables it again
+    write(&mut cpu, 0x1FFF, 0x1B);
+    assert_eq!(read(&cpu, 0xA000), 0xFF);
+    write(&mut cpu, 0x1FFF, 0x1A);
+    assert_eq!(read(&cpu, 0xA000), 0x22);
+}
+
+#[test]
+fn test_mbc3_banks() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x13, 0x06, 0x03)).unwrap();
+
+    // All seven bits select the bank, so 0x20 is reachable unlike MBC1
+    write(&mut cpu, 0x2000, 0x20);
+    assert_eq!(read(&cpu, 0x4000), 0x20);
+    write(&mut cpu, 0x2000, 0xFF);
+    assert_eq!(read(&cpu, 0x4000), 0x7F);
+    write(&mut cpu, 0x2000, 0x00);
+    assert_eq!(read(&cpu, 0x4000), 0x01);
+
+    write(&mut cpu, 0x0000, 0x0A);
+    for bank in 0..4 {
+        write(&mut cpu, 0x4000, bank);
+        write(&mut cpu, 0xA000, 0x10 + bank);
+    }
+    for bank in 0..4 {
+        write(&mut cpu, 0x4000, bank);
+        assert_eq!(read(&cpu, 0xA000), 0x10 + bank);
+    }
+}
+
+#[test]
+fn test_mbc3_rtc() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x10, 0x01, 0x02)).unwrap();
+    write(&mut cpu, 0x0000, 0x0A);
+
+    // 0x0A hours
+    write(&mut cpu, 0x4000, 0x0A);
+    write(&mut cpu, 0xA000, 5);
+    assert_eq!(read(&cpu, 0xA000), 5);
+
+    // One second of emulated time, seen only once latched
+    write(&mut cpu, 0x4000, 0x08);
+    write(&mut cpu, 0xA000, 59);
+    run_seconds(&mut cpu, 1);
+    assert_eq!(read(&cpu, 0xA000), 59);
+    write(&mut cpu, 0x6000, 0x00);
+    write(&mut cpu, 0x6000, 0x01);
+    assert_eq!(read(&cpu, 0xA000), 0);
+    write(&mut cpu, 0x4000, 0x09);
+    assert_eq!(read(&cpu, 0xA000), 1);
+
+    // The clock registers are off with RAM
+    write(&mut cpu, 0x0000, 0x00);
+    assert_eq!(read(&cpu, 0xA000), 0xFF);
+}
+
+#[test]
+fn test_mbc3_rtc_halt() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x0F, 0x01, 0x00)).unwrap();
+    write(&mut cpu, 0x0000, 0x0A);
+    write(&mut cpu, 0x4000, 0x0C);
+    write(&mut cpu, 0xA000, 0x40);
+
+    run_seconds(&mut cpu, 1);
+    write(&mut cpu, 0x6000, 0x00);
+    write(&mut cpu, 0x6000, 0x01);
+    write(&mut cpu, 0x4000, 0x08);
+    assert_eq!(read(&cpu, 0xA000), 0);
+}
+
+#[test]
+fn test_save_data() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x10, 0x01, 0x02)).unwrap();
+    write(&mut cpu, 0x0000, 0x0A);
+    write(&mut cpu, 0xA000, 0x42);
+    write(&mut cpu, 0x4000, 0x09);
+    write(&mut cpu, 0xA000, 30);
+
+    // The RAM, then 48 bytes of clock
+    let data = cpu.cartridge_mut().unwrap().save_data();
+    assert_eq!(data.len(), 0x2000 + 48);
+    assert_eq!(data[0], 0x42);
+    assert_eq!(data[0x2000 + 4], 30);
+
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x10, 0x01, 0x02)).unwrap();
+    cpu.cartridge_mut().unwrap().load_save_data(&data);
+    write(&mut cpu, 0x0000, 0x0A);
+    assert_eq!(read(&cpu, 0xA000), 0x42);
+    write(&mut cpu, 0x4000, 0x09);
+    assert_eq!(read(&cpu, 0xA000), 30);
+}
+
+#[test]
+fn test_mbc5() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x1E, 0x08, 0x04)).unwrap();
+
+    write(&mut cpu, 0x2000, 0x00);
+    assert_eq!(read(&cpu, 0x4000), 0x00);
+    write(&mut cpu, 0x2000, 0x34);
+    write(&mut cpu, 0x3000, 0x01);
+    // Bank 0x134, of which only the low byte fits in the marker
+    assert_eq!(read(&cpu, 0x4000), 0x34);
+    assert_eq!(read(&cpu, 0x4001), 0x00);
+
+    write(&mut cpu, 0x0000, 0x0A);
+    write(&mut cpu, 0x4000, 0x0F);
+    assert!(cpu.cartridge().unwrap().rumble());
+    write(&mut cpu, 0xA000, 0x77);
+    write(&mut cpu, 0x4000, 0x07);
+    assert!(!cpu.cartridge().unwrap().rumble());
+    assert_eq!(read(&cpu, 0xA000), 0x77);
+}
+
+#[test]
+fn test_mbc2() {
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x06, 0x03, 0x00)).unwrap();
+
+    write(&mut cpu, 0x2100, 0x0C);
+    assert_eq!(read(&cpu, 0x4000), 0x0C);
+
+    // Half-byte RAM, with the top bits reading as 1
+    write(&mut cpu, 0x0000, 0x0A);
+    write(&mut cpu, 0xA000, 0x5A);
+    assert_eq!(read(&cpu, 0xA000), 0xFA);
+    assert_eq!(read(&cpu, 0xA200), 0xFA);
+    assert_eq!(cpu.cartridge_mut().unwrap().save_data().len(), 0x200);
+}
+
+// A fresh directory per test, since they run in parallel
+fn save_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("yabge-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_save_file_path() {
+    let save = SaveFile::for_rom(Path::new("roms/tetris.gb"), None);
+    assert_eq!(save.path(), Path::new("roms/tetris.sav"));
+    let save = SaveFile::for_rom(Path::new("roms/tetris.gb"), Some(Path::new("saves")));
+    assert_eq!(save.path(), Path::new("saves/tetris.sav"));
+}
+
+#[test]
+fn test_save_file_flushes_on_ram_disable() {
+    let dir = save_dir("flush");
+    let mut save = SaveFile::new(dir.join("game.sav"));
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x03, 0x01, 0x02)).unwrap();
+    // Nothing there yet
+    save.load(cpu.cartridge_mut().unwrap()).unwrap();
+
+    write(&mut cpu, 0x0000, 0x0A);
+    write(&mut cpu, 0xA123, 0x99);
+    save.tick(cpu.cartridge_mut().unwrap(), 4).unwrap();
+    assert!(!save.path().exists());
+
+    write(&mut cpu, 0x0000, 0x00);
+    save.tick(cpu.cartridge_mut().unwrap(), 4).unwrap();
+    let data = fs::read(save.path()).unwrap();
+    assert_eq!(data.len(), 0x2000);
+    assert_eq!(data[0x123], 0x99);
+    assert!(!cpu.cartridge().unwrap().is_dirty());
+
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x03, 0x01, 0x02)).unwrap();
+    save.load(cpu.cartridge_mut().unwrap()).unwrap();
+    write(&mut cpu, 0x0000, 0x0A);
+    assert_eq!(read(&cpu, 0xA123), 0x99);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_save_file_flushes_periodically() {
+    let dir = save_dir("periodic");
+    let mut save = SaveFile::new(dir.join("game.sav"));
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x03, 0x01, 0x02)).unwrap();
+
+    // A game that never turns RAM back off still gets saved
+    write(&mut cpu, 0x0000, 0x0A);
+    write(&mut cpu, 0xA000, 0x01);
+    save.tick(cpu.cartridge_mut().unwrap(), 0x100000).unwrap();
+    assert!(!save.path().exists());
+    save.tick(cpu.cartridge_mut().unwrap(), 4 * 0x100000)
+        .unwrap();
+    assert!(save.path().exists());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_save_file_needs_battery() {
+    let dir = save_dir("battery");
+    let mut save = SaveFile::new(dir.join("game.sav"));
+    let mut cpu = CPU::default();
+    cpu.load_cartridge(banked_rom(0x02, 0x01, 0x02)).unwrap();
+    write(&mut cpu, 0x0000, 0x0A);
+    write(&mut cpu, 0xA000, 0x01);
+    save.flush(cpu.cartridge_mut().unwrap()).unwrap();
+    assert!(!save.path().exists());
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,25 @@
+// Helpers shared by the integration tests. Not every test file uses all of
+// them, hence the allow.
+#![allow(dead_code)]
+
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+pub fn read(cpu: &CPU, address: u16) -> u8 {
+    match cpu.read(Value::SixteenBit(address), false) {
+        Value::EightBit(data) => data,
+        Value::SixteenBit(_) => unreachable!(),
+    }
+}
+
+pub fn write(cpu: &mut CPU, address: u16, data: u8) {
+    cpu.write(Value::SixteenBit(address), Value::EightBit(data));
+}
+
+// Runs the rest of the machine for a number of M-cycles without executing
+// anything
+pub fn tick(cpu: &mut CPU, cycles: u32) {
+    for _ in 0..cycles {
+        cpu.inc_clock(1);
+    }
+}
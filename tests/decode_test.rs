@@ -0,0 +1,193 @@
+use yabge::cpu::error::EmulatorError;
+use yabge::cpu::flag::Flag::Z;
+use yabge::cpu::instruction::Condition::FlagOff;
+use yabge::cpu::instruction::{
+    AdditionalInstruction, Condition, Instruction, InstructionLength, JumpCycles,
+};
+use yabge::cpu::operand::Operand;
+use yabge::cpu::registers::Register::{A, B, BC, C, HL, PC};
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+#[test]
+fn test_decode_register_operands() {
+    assert_eq!(
+        Instruction::decode(&[0x78]),
+        (
+            Instruction::Load {
+                to: Operand::Register(A),
+                what: Operand::Register(B),
+                additional_instruction: AdditionalInstruction::None,
+                cycles: 1,
+                length: InstructionLength::One,
+            },
+            InstructionLength::One
+        )
+    );
+}
+
+#[test]
+fn test_decode_indirect_operands() {
+    assert_eq!(
+        Instruction::decode(&[0x34]),
+        (
+            Instruction::Inc {
+                what: Operand::Indirect(HL),
+                cycles: 3,
+            },
+            InstructionLength::One
+        )
+    );
+    assert_eq!(
+        Instruction::decode(&[0xE2]).0,
+        Instruction::Load {
+            to: Operand::Indirect(C),
+            what: Operand::Register(A),
+            additional_instruction: AdditionalInstruction::None,
+            cycles: 2,
+            length: InstructionLength::One,
+        }
+    );
+    assert_eq!(
+        Instruction::decode(&[0xF0, 0x44]).0,
+        Instruction::Load {
+            to: Operand::Register(A),
+            what: Operand::High(0x44),
+            additional_instruction: AdditionalInstruction::None,
+            cycles: 3,
+            length: InstructionLength::Two,
+        }
+    );
+}
+
+#[test]
+fn test_decode_immediates() {
+    assert_eq!(
+        Instruction::decode(&[0x01, 0xCD, 0xAB]),
+        (
+            Instruction::Load {
+                to: Operand::Register(BC),
+                what: Operand::Immediate16(0xABCD),
+                additional_instruction: AdditionalInstruction::None,
+                cycles: 3,
+                length: InstructionLength::Three,
+            },
+            InstructionLength::Three
+        )
+    );
+    assert_eq!(
+        Instruction::decode(&[0xEA, 0x00, 0xC0]).0,
+        Instruction::Load {
+            to: Operand::Absolute(0xC000),
+            what: Operand::Register(A),
+            additional_instruction: AdditionalInstruction::None,
+            cycles: 4,
+            length: InstructionLength::Three,
+        }
+    );
+}
+
+#[test]
+fn test_decode_jumps() {
+    assert_eq!(
+        Instruction::decode(&[0x20, 0xFE]).0,
+        Instruction::Jr {
+            how_far: -2,
+            condition: FlagOff(Z),
+            cycles: JumpCycles {
+                executed: 3,
+                not_executed: 2,
+            },
+            length: InstructionLength::Two,
+        }
+    );
+    assert_eq!(
+        Instruction::decode(&[0xCD, 0x50, 0x01]),
+        (
+            Instruction::Call {
+                to: 0x0150,
+                condition: Condition::None,
+            },
+            InstructionLength::Three
+        )
+    );
+}
+
+#[test]
+fn test_decode_prefixed() {
+    assert_eq!(
+        Instruction::decode(&[0xCB, 0x36]),
+        (
+            Instruction::Swap(Operand::Indirect(HL)),
+            InstructionLength::Two
+        )
+    );
+}
+
+#[test]
+fn test_decode_truncated() {
+    // Missing operand bytes read as 0xFF
+    assert_eq!(
+        Instruction::decode(&[0x3E]).0,
+        Instruction::Load {
+            to: Operand::Register(A),
+            what: Operand::Immediate8(0xFF),
+            additional_instruction: AdditionalInstruction::None,
+            cycles: 2,
+            length: InstructionLength::Two,
+        }
+    );
+}
+
+#[test]
+fn test_decode_lengths() {
+    let lengths = [
+        (0x00, 1), // NOP
+        (0x10, 2), // STOP
+        (0x18, 2), // JR s8
+        (0xC3, 3), // JP a16
+        (0xCB, 2), // prefixed
+        (0xD3, 1), // illegal
+        (0xE0, 2), // LD (a8), A
+        (0xE9, 1), // JP HL
+    ];
+    for (code, length) in lengths {
+        assert_eq!(Instruction::decode(&[code, 0x00, 0x00]).1.count(), length);
+    }
+}
+
+#[test]
+fn test_operands_resolved_at_execute_time() {
+    let mut cpu = CPU::default();
+    let (instruction, _) = Instruction::decode(&[0x7E]); // LD A, (HL)
+
+    cpu.registers.set(HL, Value::SixteenBit(0xC010));
+    cpu.write(Value::SixteenBit(0xC010), Value::EightBit(0x42));
+    cpu.execute(instruction).unwrap();
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x42));
+
+    // The same decoded instruction follows HL around
+    cpu.registers.set(HL, Value::SixteenBit(0xC020));
+    cpu.write(Value::SixteenBit(0xC020), Value::EightBit(0x24));
+    cpu.execute(instruction).unwrap();
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x24));
+}
+
+#[test]
+fn test_write_to_immediate() {
+    let mut cpu = CPU::default();
+    cpu.registers.set(PC, Value::SixteenBit(0x0150));
+
+    let instruction = Instruction::Inc {
+        what: Operand::Immediate8(0x01),
+        cycles: 1,
+    };
+
+    assert_eq!(
+        cpu.execute(instruction),
+        Err(EmulatorError::UnsupportedOperand {
+            operand: Operand::Immediate8(0x01),
+            pc: 0x0150,
+        })
+    );
+}
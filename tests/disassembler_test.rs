@@ -0,0 +1,119 @@
+use yabge::cpu::instruction::Instruction;
+use yabge::disassembler::{disassemble, mnemonic};
+
+fn disassemble_at(bytes: &[u8], address: u16) -> String {
+    let (instruction, _) = Instruction::decode(bytes);
+    mnemonic(&instruction, address)
+}
+
+// A blank 32KB ROM, with `program` at the 0x0100 entry point
+fn rom_with(program: &[u8]) -> Vec<u8> {
+    let mut rom = vec![0x00; 0x8000];
+    // RETI at every interrupt vector
+    for vector in [0x40, 0x48, 0x50, 0x58, 0x60] {
+        rom[vector] = 0xD9;
+    }
+    rom[0x100..0x100 + program.len()].copy_from_slice(program);
+    rom
+}
+
+#[test]
+fn test_mnemonics() {
+    assert_eq!(disassemble_at(&[0x00], 0), "nop");
+    assert_eq!(disassemble_at(&[0x01, 0xCD, 0xAB], 0), "ld bc, $ABCD");
+    assert_eq!(disassemble_at(&[0x08, 0x00, 0xC0], 0), "ld [$C000], sp");
+    assert_eq!(disassemble_at(&[0x0A], 0), "ld a, [bc]");
+    assert_eq!(disassemble_at(&[0x07], 0), "rlca");
+    assert_eq!(disassemble_at(&[0x1F], 0), "rra");
+    assert_eq!(disassemble_at(&[0x22], 0), "ld [hl+], a");
+    assert_eq!(disassemble_at(&[0x3A], 0), "ld a, [hl-]");
+    assert_eq!(disassemble_at(&[0x35], 0), "dec [hl]");
+    assert_eq!(disassemble_at(&[0x40], 0), "ld b, b");
+    assert_eq!(disassemble_at(&[0x86], 0), "add a, [hl]");
+    assert_eq!(disassemble_at(&[0x09], 0), "add hl, bc");
+    assert_eq!(disassemble_at(&[0x9E], 0), "sbc a, [hl]");
+    assert_eq!(disassemble_at(&[0xEE, 0x0F], 0), "xor a, $0F");
+    assert_eq!(disassemble_at(&[0xE0, 0x40], 0), "ldh [$FF40], a");
+    assert_eq!(disassemble_at(&[0xF2], 0), "ldh a, [c]");
+    assert_eq!(disassemble_at(&[0xFA, 0x34, 0x12], 0), "ld a, [$1234]");
+    assert_eq!(disassemble_at(&[0xE8, 0xFE], 0), "add sp, -2");
+    assert_eq!(disassemble_at(&[0xF8, 0x05], 0), "ld hl, sp+5");
+    assert_eq!(disassemble_at(&[0xF5], 0), "push af");
+    assert_eq!(disassemble_at(&[0xFF], 0), "rst $38");
+    assert_eq!(disassemble_at(&[0xD3], 0), "db $D3");
+}
+
+#[test]
+fn test_prefixed_mnemonics() {
+    assert_eq!(disassemble_at(&[0xCB, 0x00], 0), "rlc b");
+    assert_eq!(disassemble_at(&[0xCB, 0x16], 0), "rl [hl]");
+    assert_eq!(disassemble_at(&[0xCB, 0x2F], 0), "sra a");
+    assert_eq!(disassemble_at(&[0xCB, 0x37], 0), "swap a");
+    assert_eq!(disassemble_at(&[0xCB, 0x38], 0), "srl b");
+    assert_eq!(disassemble_at(&[0xCB, 0x7E], 0), "bit 7, [hl]");
+    assert_eq!(disassemble_at(&[0xCB, 0x87], 0), "res 0, a");
+    assert_eq!(disassemble_at(&[0xCB, 0xFF], 0), "set 7, a");
+}
+
+#[test]
+fn test_jump_targets() {
+    // Relative jumps count from the end of the instruction
+    assert_eq!(disassemble_at(&[0x18, 0xFE], 0x0150), "jr $0150");
+    assert_eq!(disassemble_at(&[0x20, 0x10], 0x0150), "jr nz, $0162");
+    assert_eq!(disassemble_at(&[0x38, 0x80], 0x0150), "jr c, $00D2");
+    assert_eq!(disassemble_at(&[0xC3, 0x50, 0x01], 0), "jp $0150");
+    assert_eq!(disassemble_at(&[0xD2, 0x00, 0x40], 0), "jp nc, $4000");
+    assert_eq!(disassemble_at(&[0xE9], 0), "jp hl");
+    assert_eq!(disassemble_at(&[0xCC, 0x00, 0x20], 0), "call z, $2000");
+    assert_eq!(disassemble_at(&[0xC0], 0), "ret nz");
+}
+
+#[test]
+fn test_every_opcode_disassembles() {
+    for code in 0..=0xFFu8 {
+        assert!(!disassemble_at(&[code, 0x00, 0x00], 0).is_empty());
+        assert!(!disassemble_at(&[0xCB, code], 0).is_empty());
+    }
+}
+
+#[test]
+fn test_follows_reachable_code() {
+    let mut rom = rom_with(&[
+        0x00, // 0100 nop
+        0xC3, 0x50, 0x01, // 0101 jp $0150
+        0xDE, 0xAD, // 0104 data, never reached
+    ]);
+    rom[0x150..0x15A].copy_from_slice(&[
+        0x28, 0x03, // 0150 jr z, $0155
+        0xCD, 0x60, 0x01, // 0152 call $0160
+        0xC3, 0x50, 0x01, // 0155 jp $0150
+        0xDE, 0xAD, // 0158 data, never reached
+    ]);
+    rom[0x160] = 0xC9; // 0160 ret
+
+    let addresses: Vec<u16> = disassemble(&rom)
+        .iter()
+        .map(|line| line.address)
+        .filter(|address| *address >= 0x100)
+        .collect();
+    assert_eq!(addresses, vec![0x100, 0x101, 0x150, 0x152, 0x155, 0x160]);
+}
+
+#[test]
+fn test_interrupt_vectors_are_entry_points() {
+    let rom = rom_with(&[0x76, 0x18, 0xFD]); // halt, jr $0100
+
+    let lines = disassemble(&rom);
+    let addresses: Vec<u16> = lines.iter().map(|line| line.address).collect();
+    assert_eq!(addresses, vec![0x40, 0x48, 0x50, 0x58, 0x60, 0x100, 0x101]);
+}
+
+#[test]
+fn test_line_format() {
+    let rom = rom_with(&[0xC3, 0x50, 0x01]);
+    let lines = disassemble(&rom);
+    let entry = lines.iter().find(|line| line.address == 0x100).unwrap();
+
+    assert_eq!(entry.bytes, vec![0xC3, 0x50, 0x01]);
+    assert_eq!(entry.to_string(), "0100  C3 50 01  jp $0150");
+}
@@ -0,0 +1,100 @@
+use yabge::cpu::registers::Register::{PC, SP};
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+mod common;
+
+use common::{read, tick, write};
+
+// 160 bytes of something recognisable at 0xC100
+fn fill_source(cpu: &mut CPU) {
+    for offset in 0..0xA0 {
+        write(cpu, 0xC100 + offset, offset as u8 ^ 0x5A);
+    }
+}
+
+fn oam(cpu: &CPU) -> Vec<u8> {
+    (0xFE00..0xFEA0).map(|address| read(cpu, address)).collect()
+}
+
+fn source() -> Vec<u8> {
+    (0..0xA0).map(|offset| offset as u8 ^ 0x5A).collect()
+}
+
+#[test]
+fn test_dma_copies_into_oam() {
+    let mut cpu = CPU::default();
+    fill_source(&mut cpu);
+    write(&mut cpu, 0xFF46, 0xC1);
+    tick(&mut cpu, 160);
+    assert_eq!(oam(&cpu), source());
+    assert_eq!(read(&cpu, 0xFF46), 0xC1);
+}
+
+#[test]
+fn test_dma_locks_out_everything_but_hram() {
+    let mut cpu = CPU::default();
+    fill_source(&mut cpu);
+    write(&mut cpu, 0xFF80, 0x42);
+    write(&mut cpu, 0xC000, 0x11);
+    write(&mut cpu, 0xFF46, 0xC1);
+
+    tick(&mut cpu, 80);
+    assert_eq!(read(&cpu, 0xC000), 0xFF);
+    assert_eq!(read(&cpu, 0xC100), 0xFF);
+    assert_eq!(read(&cpu, 0xFE00), 0xFF);
+    assert_eq!(read(&cpu, 0xFF80), 0x42);
+    assert_eq!(read(&cpu, 0xFF46), 0xC1);
+    write(&mut cpu, 0xC000, 0x22);
+    write(&mut cpu, 0xFF81, 0x43);
+
+    tick(&mut cpu, 79);
+    assert_eq!(read(&cpu, 0xC000), 0xFF);
+    tick(&mut cpu, 1);
+    assert_eq!(read(&cpu, 0xC000), 0x11);
+    assert_eq!(read(&cpu, 0xFF81), 0x43);
+}
+
+#[test]
+fn test_dma_from_echo_ram_region() {
+    let mut cpu = CPU::default();
+    for offset in 0..0xA0 {
+        write(&mut cpu, 0xDE00 + offset, offset as u8);
+    }
+    // Sources past 0xDFFF come from work RAM
+    write(&mut cpu, 0xFF46, 0xFE);
+    tick(&mut cpu, 160);
+    assert_eq!(oam(&cpu), (0..0xA0).collect::<Vec<u8>>());
+}
+
+#[test]
+fn test_dma_routine_in_hram() {
+    let mut cpu = CPU::default();
+    fill_source(&mut cpu);
+    // The usual routine: start the transfer and spin until it's done, all
+    // without leaving HRAM
+    let routine = [
+        0x3E, 0xC1, // LD A, 0xC1
+        0xE0, 0x46, // LDH (0x46), A
+        0x3E, 0x28, // LD A, 40
+        0x3D, // DEC A
+        0xC2, 0x86, 0xFF, // JP NZ, 0xFF86
+        0xC9, // RET
+    ];
+    for (offset, byte) in routine.iter().enumerate() {
+        write(&mut cpu, 0xFF80 + offset as u16, *byte);
+    }
+    // CALL 0xFF80 from work RAM, with the stack there too
+    write(&mut cpu, 0xC000, 0xCD);
+    write(&mut cpu, 0xC001, 0x80);
+    write(&mut cpu, 0xC002, 0xFF);
+    cpu.registers.set(PC, Value::SixteenBit(0xC000));
+    cpu.registers.set(SP, Value::SixteenBit(0xDFFE));
+
+    while cpu.registers.get(PC) != Value::SixteenBit(0xC003) {
+        cpu.step().unwrap();
+        assert!(cpu.clock() < 1000);
+    }
+    assert_eq!(oam(&cpu), source());
+    assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0xDFFE));
+}
@@ -1,14 +1,14 @@
 use yabge::cpu::flag::Flag::{C, H, N, Z};
-use yabge::cpu::instruction::BitAddr::{Five, Six, Three, Two};
+use yabge::cpu::instruction::BitAddr::{Six, Three, Two};
 use yabge::cpu::instruction::Condition::FlagOn;
 use yabge::cpu::instruction::{
-    AdditionalInstruction, BitAddr, Condition, Instruction, InstructionLength, JumpCycles,
-    RotateDirection,
+    AdditionalInstruction, Condition, Instruction, InstructionLength, JumpCycles, RotateDirection,
 };
+use yabge::cpu::operand::Operand;
 use yabge::cpu::registers::Register;
 use yabge::cpu::registers::Register::{A, AF, B, BC, HL, PC, SP};
 use yabge::cpu::value::Value;
-use yabge::cpu::{MemoryLocation, CPU};
+use yabge::cpu::CPU;
 
 #[test]
 fn test_load_reg() {
@@ -16,13 +16,13 @@ fn test_load_reg() {
     cpu.registers.set(HL, Value::SixteenBit(0x1234));
 
     let instruction = Instruction::Load {
-        to: MemoryLocation::Register(SP),
-        what: cpu.registers.get(HL),
+        to: Operand::Register(SP),
+        what: Operand::Register(HL),
         additional_instruction: AdditionalInstruction::None,
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0x1234));
     assert!(!cpu.registers.f.is_set(C));
@@ -37,12 +37,12 @@ fn test_add() {
     cpu.registers.set(Register::B, Value::EightBit(0x23));
 
     let instruction = Instruction::Add {
-        to: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        to: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x61));
     assert!(!cpu.registers.f.is_set(C));
@@ -56,12 +56,12 @@ fn test_adc_with_carry() {
     cpu.registers.set(A, Value::EightBit(0x3E));
     cpu.registers.set(Register::B, Value::EightBit(0x23));
     let instruction = Instruction::Adc {
-        to: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        to: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x62));
     assert!(!cpu.registers.f.is_set(C));
@@ -74,12 +74,12 @@ fn test_adc_no_carry() {
     cpu.registers.set(A, Value::EightBit(0x3E));
     cpu.registers.set(Register::B, Value::EightBit(0x23));
     let instruction = Instruction::Adc {
-        to: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        to: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x61));
     assert!(!cpu.registers.f.is_set(C));
@@ -94,12 +94,12 @@ fn test_sub() {
     cpu.registers.set(B, Value::EightBit(0x1F));
 
     let instruction = Instruction::Sub {
-        from: MemoryLocation::Register(A),
-        what: cpu.registers.get(B),
+        from: Operand::Register(A),
+        what: Operand::Register(B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0xD3));
     assert!(!cpu.registers.f.is_set(C));
@@ -114,12 +114,12 @@ fn test_sub_to_zero() {
     cpu.registers.set(B, Value::EightBit(0x50));
 
     let instruction = Instruction::Sub {
-        from: MemoryLocation::Register(A),
-        what: cpu.registers.get(B),
+        from: Operand::Register(A),
+        what: Operand::Register(B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x00));
     assert!(!cpu.registers.f.is_set(C));
@@ -134,12 +134,12 @@ fn test_sbc_with_carry() {
     cpu.registers.set(A, Value::EightBit(0x3E));
     cpu.registers.set(B, Value::EightBit(0x23));
     let instruction = Instruction::Sbc {
-        from: MemoryLocation::Register(A),
-        what: cpu.registers.get(B),
+        from: Operand::Register(A),
+        what: Operand::Register(B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x1A));
     assert!(!cpu.registers.f.is_set(C));
@@ -152,12 +152,12 @@ fn test_sbc_no_carry() {
     cpu.registers.set(A, Value::EightBit(0x3E));
     cpu.registers.set(B, Value::EightBit(0x23));
     let instruction = Instruction::Sbc {
-        from: MemoryLocation::Register(A),
-        what: cpu.registers.get(B),
+        from: Operand::Register(A),
+        what: Operand::Register(B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x1B));
     assert!(!cpu.registers.f.is_set(C));
@@ -170,10 +170,10 @@ fn test_inc_eight_bit() {
     cpu.registers.set(A, Value::EightBit(0x3E));
 
     let instruction = Instruction::Inc {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         cycles: 4,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x3F));
     assert!(!cpu.registers.f.is_set(N));
@@ -186,16 +186,17 @@ fn test_inc_sixteen_bit() {
         .set(BC, Value::SixteenBit(0b0000_0000_1111_1111));
 
     let instruction = Instruction::Inc {
-        what: MemoryLocation::Register(BC),
+        what: Operand::Register(BC),
         cycles: 4,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(
         cpu.registers.get(Register::BC),
         Value::SixteenBit(0b0000_0001_0000_0000)
     );
-    assert!(cpu.registers.f.is_set(H));
+    // 16 bit INC doesn't touch the flags
+    assert!(!cpu.registers.f.is_set(H));
     assert!(!cpu.registers.f.is_set(N));
     assert!(!cpu.registers.f.is_set(C));
 }
@@ -206,10 +207,10 @@ fn test_dec_eight_bit() {
     cpu.registers.set(A, Value::EightBit(0x00));
 
     let instruction = Instruction::Dec {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         cycles: 4,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0xFF));
     assert!(cpu.registers.f.is_set(N));
@@ -221,13 +222,197 @@ fn test_dec_sixteen_bit() {
     cpu.registers.set(BC, Value::SixteenBit(0x1234));
 
     let instruction = Instruction::Dec {
-        what: MemoryLocation::Register(BC),
+        what: Operand::Register(BC),
         cycles: 4,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(BC), Value::SixteenBit(0x1233));
+    assert!(!cpu.registers.f.is_set(N));
+
+    // Not even when it reaches zero
+    cpu.registers.set(BC, Value::SixteenBit(0x0001));
+    cpu.registers.f.set(C);
+    cpu.execute(instruction).unwrap();
+
+    assert_eq!(cpu.registers.get(BC), Value::SixteenBit(0x0000));
+    assert!(!cpu.registers.f.is_set(Z));
+    assert!(!cpu.registers.f.is_set(N));
+    assert!(cpu.registers.f.is_set(C));
+}
+
+#[test]
+fn test_inc_pointer() {
+    let mut cpu: CPU = Default::default();
+    cpu.registers.f.set(C);
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0x0F));
+
+    let instruction = Instruction::Inc {
+        what: Operand::Absolute(0xC000),
+        cycles: 3,
+    };
+    cpu.execute(instruction).unwrap();
+
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC000), false),
+        Value::EightBit(0x10)
+    );
+    assert!(cpu.registers.f.is_set(H));
+    assert!(!cpu.registers.f.is_set(N));
+    assert!(!cpu.registers.f.is_set(Z));
+    // INC leaves the carry flag alone
+    assert!(cpu.registers.f.is_set(C));
+}
+
+#[test]
+fn test_inc_pointer_to_zero() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0xFF));
+
+    let instruction = Instruction::Inc {
+        what: Operand::Absolute(0xC000),
+        cycles: 3,
+    };
+    cpu.execute(instruction).unwrap();
+
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC000), false),
+        Value::EightBit(0x00)
+    );
+    assert!(cpu.registers.f.is_set(Z));
+    assert!(cpu.registers.f.is_set(H));
+    assert!(!cpu.registers.f.is_set(C));
+}
+
+#[test]
+fn test_dec_pointer() {
+    let mut cpu: CPU = Default::default();
+    cpu.registers.f.set(C);
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0x00));
+
+    let instruction = Instruction::Dec {
+        what: Operand::Absolute(0xC000),
+        cycles: 3,
+    };
+    cpu.execute(instruction).unwrap();
+
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC000), false),
+        Value::EightBit(0xFF)
+    );
+    assert!(cpu.registers.f.is_set(N));
+    assert!(cpu.registers.f.is_set(H));
+    assert!(cpu.registers.f.is_set(C));
+}
+
+#[test]
+fn test_dec_pointer_to_zero() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0x01));
+
+    let instruction = Instruction::Dec {
+        what: Operand::Absolute(0xC000),
+        cycles: 3,
+    };
+    cpu.execute(instruction).unwrap();
+
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC000), false),
+        Value::EightBit(0x00)
+    );
+    assert!(cpu.registers.f.is_set(Z));
     assert!(cpu.registers.f.is_set(N));
+    assert!(!cpu.registers.f.is_set(H));
+    assert!(!cpu.registers.f.is_set(C));
+}
+
+#[test]
+fn test_add_pointer() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0x3E));
+
+    let instruction = Instruction::Add {
+        to: Operand::Absolute(0xC000),
+        what: Operand::Immediate8(0x23),
+        cycles: 2,
+        length: InstructionLength::One,
+    };
+    cpu.execute(instruction).unwrap();
+
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC000), false),
+        Value::EightBit(0x61)
+    );
+    assert!(!cpu.registers.f.is_set(C));
+    assert!(cpu.registers.f.is_set(H));
+}
+
+#[test]
+fn test_adc_pointer() {
+    let mut cpu: CPU = Default::default();
+    cpu.registers.f.set(C);
+    cpu.registers.set(A, Value::EightBit(0x00));
+    cpu.registers.set(HL, Value::SixteenBit(0xC000));
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0xFF));
+
+    let instruction = Instruction::Adc {
+        to: Operand::Register(A),
+        what: Operand::Indirect(HL),
+        cycles: 2,
+        length: InstructionLength::One,
+    };
+    cpu.execute(instruction).unwrap();
+
+    // 0xFF plus the carry overflows, which has to show up in H and C
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x00));
+    assert!(cpu.registers.f.is_set(Z));
+    assert!(!cpu.registers.f.is_set(N));
+    assert!(cpu.registers.f.is_set(H));
+    assert!(cpu.registers.f.is_set(C));
+}
+
+#[test]
+fn test_sub_pointer() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0xF2));
+
+    let instruction = Instruction::Sub {
+        from: Operand::Absolute(0xC000),
+        what: Operand::Immediate8(0x1F),
+        cycles: 2,
+        length: InstructionLength::One,
+    };
+    cpu.execute(instruction).unwrap();
+
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC000), false),
+        Value::EightBit(0xD3)
+    );
+    assert!(!cpu.registers.f.is_set(C));
+    assert!(cpu.registers.f.is_set(H));
+}
+
+#[test]
+fn test_sbc_pointer() {
+    let mut cpu: CPU = Default::default();
+    cpu.registers.f.set(C);
+    cpu.registers.set(A, Value::EightBit(0x00));
+    cpu.registers.set(HL, Value::SixteenBit(0xC000));
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0xFF));
+
+    let instruction = Instruction::Sbc {
+        from: Operand::Register(A),
+        what: Operand::Indirect(HL),
+        cycles: 2,
+        length: InstructionLength::One,
+    };
+    cpu.execute(instruction).unwrap();
+
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x00));
+    assert!(cpu.registers.f.is_set(Z));
+    assert!(cpu.registers.f.is_set(N));
+    assert!(cpu.registers.f.is_set(H));
+    assert!(cpu.registers.f.is_set(C));
 }
 
 #[test]
@@ -235,13 +420,13 @@ fn test_rotate_left() {
     let mut cpu: CPU = Default::default();
     cpu.registers.set(A, Value::EightBit(0b1100_0011));
     let instruction = Instruction::Rot {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         direction: RotateDirection::Left,
         use_carry: false,
         cycles: 4,
         length: InstructionLength::Two,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1000_0111));
     assert!(cpu.registers.f.is_set(C));
 }
@@ -252,13 +437,13 @@ fn test_rotate_right_carry() {
     cpu.registers.set(B, Value::EightBit(0b1100_0010));
     cpu.registers.f.set(C);
     let instruction = Instruction::Rot {
-        what: MemoryLocation::Register(B),
+        what: Operand::Register(B),
         direction: RotateDirection::Right,
         use_carry: true,
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(B), Value::EightBit(0b1110_0001));
     assert!(!cpu.registers.f.is_set(C));
 }
@@ -269,13 +454,13 @@ fn test_rotate_left_carry() {
     cpu.registers.set(A, Value::EightBit(0b0100_1000));
     cpu.registers.f.set(C);
     let instruction = Instruction::Rot {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         direction: RotateDirection::Left,
         use_carry: true,
         cycles: 4,
         length: InstructionLength::Two,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1001_0001));
     assert!(!cpu.registers.f.is_set(C));
 }
@@ -287,7 +472,7 @@ fn test_jr() {
     cpu.registers.f.set(C);
 
     let instruction = Instruction::Jr {
-        how_far: Value::EightBit(0b1001_1001),
+        how_far: 0b1001_1001u8 as i8,
         condition: Condition::FlagOn(C),
         cycles: JumpCycles {
             executed: 2,
@@ -295,8 +480,8 @@ fn test_jr() {
         },
         length: InstructionLength::Two,
     };
-    cpu.execute(instruction);
-    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x11CD));
+    cpu.execute(instruction).unwrap();
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x11CF));
     assert!(cpu.registers.f.is_set(C));
 }
 
@@ -306,7 +491,7 @@ fn test_jp() {
     cpu.registers.set(PC, Value::SixteenBit(0x1234));
 
     let instruction = Instruction::Jp {
-        to: Value::SixteenBit(0x5678),
+        to: Operand::Immediate16(0x5678),
         condition: Condition::FlagOff(Z),
         cycles: JumpCycles {
             executed: 2,
@@ -314,7 +499,7 @@ fn test_jp() {
         },
         length: InstructionLength::Two,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x5678));
 }
 
@@ -327,18 +512,18 @@ fn test_daa_after_add_1() {
     cpu.registers.set(Register::B, Value::EightBit(0x28));
 
     let instruction = Instruction::Add {
-        to: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        to: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x7C));
     assert!(!cpu.registers.f.is_set(C));
 
     // DAA Correction
-    cpu.execute(Instruction::Daa);
+    cpu.execute(Instruction::Daa).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x82));
     assert!(!cpu.registers.f.is_set(C));
 }
@@ -352,19 +537,19 @@ fn test_daa_after_add_2() {
     cpu.registers.set(Register::B, Value::EightBit(0x04));
 
     let instruction = Instruction::Add {
-        to: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        to: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x9C));
     assert!(!cpu.registers.f.is_set(C));
     assert!(!cpu.registers.f.is_set(H));
 
     // DAA Correction
-    cpu.execute(Instruction::Daa);
+    cpu.execute(Instruction::Daa).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x02));
     assert!(cpu.registers.f.is_set(C));
     assert!(!cpu.registers.f.is_set(H));
@@ -379,18 +564,18 @@ fn test_daa_after_add_3() {
     cpu.registers.set(Register::B, Value::EightBit(0x90));
 
     let instruction = Instruction::Add {
-        to: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        to: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x10));
     assert!(cpu.registers.f.is_set(C));
 
     // DAA Correction
-    cpu.execute(Instruction::Daa);
+    cpu.execute(Instruction::Daa).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x70));
 }
 
@@ -403,19 +588,19 @@ fn test_daa_after_add_4() {
     cpu.registers.set(Register::B, Value::EightBit(0x28));
 
     let instruction = Instruction::Add {
-        to: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        to: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x41));
     assert!(cpu.registers.f.is_set(H));
     assert!(!cpu.registers.f.is_set(C));
 
     // DAA Correction
-    cpu.execute(Instruction::Daa);
+    cpu.execute(Instruction::Daa).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x47));
 }
 
@@ -428,19 +613,19 @@ fn test_daa_after_sub_1() {
     cpu.registers.set(Register::B, Value::EightBit(0x28));
 
     let instruction = Instruction::Sub {
-        from: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        from: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x1F));
     assert!(cpu.registers.f.is_set(H));
     assert!(!cpu.registers.f.is_set(C));
 
     // DAA Correction
-    cpu.execute(Instruction::Daa);
+    cpu.execute(Instruction::Daa).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x19));
 }
 
@@ -453,19 +638,19 @@ fn test_daa_after_sub_2() {
     cpu.registers.set(Register::B, Value::EightBit(0x13));
 
     let instruction = Instruction::Sub {
-        from: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        from: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x0D));
     assert!(cpu.registers.f.is_set(H));
     assert!(!cpu.registers.f.is_set(C));
 
     // DAA Correction
-    cpu.execute(Instruction::Daa);
+    cpu.execute(Instruction::Daa).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x07));
 }
 
@@ -478,19 +663,19 @@ fn test_daa_after_sub_3() {
     cpu.registers.set(Register::B, Value::EightBit(0x21));
 
     let instruction = Instruction::Sub {
-        from: MemoryLocation::Register(A),
-        what: cpu.registers.get(Register::B),
+        from: Operand::Register(A),
+        what: Operand::Register(Register::B),
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0xE4));
     assert!(!cpu.registers.f.is_set(H));
     assert!(cpu.registers.f.is_set(C));
 
     // DAA Correction
-    cpu.execute(Instruction::Daa);
+    cpu.execute(Instruction::Daa).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x84));
 }
 
@@ -498,7 +683,7 @@ fn test_daa_after_sub_3() {
 fn test_cpl() {
     let mut cpu: CPU = Default::default();
     cpu.registers.set(A, Value::EightBit(0b0011_0101));
-    cpu.execute(Instruction::Cpl);
+    cpu.execute(Instruction::Cpl).unwrap();
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1100_1010));
 }
@@ -507,7 +692,7 @@ fn test_cpl() {
 fn test_nop() {
     let mut cpu: CPU = Default::default();
     let instruction = Instruction::Nop;
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(1));
 }
@@ -517,13 +702,13 @@ fn test_rotate_right() {
     let mut cpu: CPU = Default::default();
     cpu.registers.set(A, Value::EightBit(0b1100_0011));
     let instruction = Instruction::Rot {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         direction: RotateDirection::Right,
         use_carry: false,
         cycles: 4,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1110_0001));
     assert!(cpu.registers.f.is_set(C));
 }
@@ -534,11 +719,11 @@ fn test_and() {
     cpu.registers.set(A, Value::EightBit(0b1100_0011));
     cpu.registers.set(B, Value::EightBit(0b0100_1110));
     let instruction = Instruction::And {
-        what: cpu.registers.get(B),
+        what: Operand::Register(B),
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b0100_0010));
 
     // -- // -- // -- //
@@ -546,11 +731,11 @@ fn test_and() {
     cpu.registers.set(A, Value::EightBit(0b1100_0011));
     cpu.registers.set(B, Value::EightBit(0b0011_1100));
     let instruction = Instruction::And {
-        what: cpu.registers.get(B),
+        what: Operand::Register(B),
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0));
     assert!(cpu.registers.f.is_set(Z))
 }
@@ -561,11 +746,11 @@ fn test_xor() {
     cpu.registers.set(A, Value::EightBit(0b1100_0011));
     cpu.registers.set(B, Value::EightBit(0b0100_1110));
     let instruction = Instruction::Xor {
-        what: cpu.registers.get(B),
+        what: Operand::Register(B),
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1000_1101));
 
     // -- // -- // -- //
@@ -573,11 +758,11 @@ fn test_xor() {
     cpu.registers.set(A, Value::EightBit(0b1111_0111));
     cpu.registers.set(B, Value::EightBit(0b1011_1100));
     let instruction = Instruction::Xor {
-        what: cpu.registers.get(B),
+        what: Operand::Register(B),
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b0100_1011));
 }
 
@@ -587,11 +772,11 @@ fn test_or() {
     cpu.registers.set(A, Value::EightBit(0b1100_0011));
     cpu.registers.set(B, Value::EightBit(0b0100_1110));
     let instruction = Instruction::Or {
-        what: cpu.registers.get(B),
+        what: Operand::Register(B),
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1100_1111));
 
     // -- // -- // -- //
@@ -599,11 +784,11 @@ fn test_or() {
     cpu.registers.set(A, Value::EightBit(0b1111_0111));
     cpu.registers.set(B, Value::EightBit(0b1011_1100));
     let instruction = Instruction::Or {
-        what: cpu.registers.get(B),
+        what: Operand::Register(B),
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1111_1111));
 }
 
@@ -613,8 +798,8 @@ fn test_swap() {
     cpu.registers.set(HL, Value::SixteenBit(0xFACE));
     cpu.write(Value::SixteenBit(0xFACE), Value::EightBit(0b1011_1010));
 
-    let instruction = Instruction::Swap(MemoryLocation::Pointer(cpu.registers.get(HL)));
-    cpu.execute(instruction);
+    let instruction = Instruction::Swap(Operand::Indirect(HL));
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(
         cpu.read(Value::SixteenBit(0xFACE), false),
@@ -624,8 +809,8 @@ fn test_swap() {
     // -- // -- // -- //
     let mut cpu: CPU = Default::default();
     cpu.registers.set(A, Value::EightBit(0b1111_0111));
-    let instruction = Instruction::Swap(MemoryLocation::Register(A));
-    cpu.execute(instruction);
+    let instruction = Instruction::Swap(Operand::Register(A));
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b0111_1111));
 }
 
@@ -636,13 +821,13 @@ fn test_shift() {
     cpu.write(Value::SixteenBit(0xFACE), Value::EightBit(0b1011_1010));
 
     let instruction = Instruction::Shift {
-        what: MemoryLocation::Pointer(cpu.registers.get(HL)),
+        what: Operand::Indirect(HL),
         direction: RotateDirection::Right,
         arithmetic: true,
         cycles: 2,
         length: InstructionLength::Two,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(
         cpu.read(Value::SixteenBit(0xFACE), false),
@@ -654,13 +839,13 @@ fn test_shift() {
     let mut cpu: CPU = Default::default();
     cpu.registers.set(A, Value::EightBit(0b0011_0111));
     let instruction = Instruction::Shift {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         direction: RotateDirection::Left,
         arithmetic: false,
         cycles: 2,
         length: InstructionLength::Two,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b0110_1110));
 }
 
@@ -670,11 +855,11 @@ fn test_cp() {
     cpu.registers.set(A, Value::EightBit(0x20));
     cpu.registers.set(B, Value::EightBit(0x40));
     let instruction = Instruction::Cp {
-        what: cpu.registers.get(B),
+        what: Operand::Register(B),
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x20));
     assert!(cpu.registers.f.is_set(C));
 
@@ -683,11 +868,11 @@ fn test_cp() {
     cpu.registers.set(A, Value::EightBit(0x30));
     cpu.registers.set(B, Value::EightBit(0x30));
     let instruction = Instruction::Cp {
-        what: cpu.registers.get(B),
+        what: Operand::Register(B),
         cycles: 1,
         length: InstructionLength::One,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0x30));
     assert!(cpu.registers.f.is_set(Z))
 }
@@ -703,7 +888,7 @@ fn test_ret() {
     cpu.write(Value::SixteenBit(0x2001), Value::EightBit(0x18));
 
     let instruction = Instruction::Ret(FlagOn(Z));
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0x2002));
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x18B5));
@@ -720,7 +905,7 @@ fn test_ret() {
     cpu.write(Value::SixteenBit(0xFACF), Value::EightBit(0xBE));
 
     let instruction = Instruction::Ret(FlagOn(C));
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0xFACE));
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0xCAFE));
@@ -728,7 +913,7 @@ fn test_ret() {
 
     cpu.registers.f.set(C);
     let instruction = Instruction::Ret(FlagOn(C));
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0xFAD0));
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0xBEAD));
@@ -747,7 +932,7 @@ fn test_reti() {
     cpu.write(Value::SixteenBit(0x2001), Value::EightBit(0x18));
 
     let instruction = Instruction::Reti;
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0x2002));
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x18B5));
@@ -763,7 +948,7 @@ fn test_pop() {
     cpu.write(Value::SixteenBit(0x1001), Value::EightBit(0x33));
 
     let instruction = Instruction::Pop(BC);
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0x1002));
     assert_eq!(cpu.registers.get(BC), Value::SixteenBit(0x3355));
@@ -776,7 +961,7 @@ fn test_push() {
     cpu.registers.set(SP, Value::SixteenBit(0x1007));
 
     let instruction = Instruction::Push(AF);
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(
         cpu.read(Value::SixteenBit(0x1006), false),
@@ -800,8 +985,11 @@ fn test_call() {
     cpu.write(Value::SixteenBit(0x1A48), Value::EightBit(0x35));
     cpu.write(Value::SixteenBit(0x1A49), Value::EightBit(0x21));
 
-    let instruction = Instruction::Call(FlagOn(C));
-    cpu.execute(instruction);
+    let instruction = Instruction::Call {
+        to: 0x2135,
+        condition: FlagOn(C),
+    };
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(
         cpu.read(Value::SixteenBit(0x3000), false),
@@ -817,12 +1005,12 @@ fn test_rst() {
     cpu.registers.set(PC, Value::SixteenBit(0x15B3));
 
     let instruction = Instruction::Rst(Three);
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0018));
 
     let instruction = Instruction::Rst(Two);
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
 
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0010));
 }
@@ -831,15 +1019,17 @@ fn test_rst() {
 fn test_ei() {
     let mut cpu: CPU = Default::default();
     let instruction = Instruction::Ei;
-    cpu.execute(instruction);
-    assert!(cpu.ime());
+    cpu.execute(instruction).unwrap();
+    // Only takes effect after the next instruction
+    assert!(!cpu.ime());
+    assert!(cpu.ime_next());
 }
 
 #[test]
 fn test_di() {
     let mut cpu: CPU = Default::default();
     let instruction = Instruction::Di;
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert!(!cpu.ime());
 }
 
@@ -849,10 +1039,10 @@ fn test_bit() {
     cpu.registers.set(A, Value::EightBit(0b0010_0000));
 
     let instruction = Instruction::Bit {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         bit: Six,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert!(cpu.registers.f.is_set(Z));
 
     // -- // -- // -- // -- //
@@ -862,10 +1052,10 @@ fn test_bit() {
     cpu.registers.set(HL, Value::SixteenBit(0xFACE));
     cpu.write(Value::SixteenBit(0xFACE), Value::EightBit(0b0000_1000));
     let instruction = Instruction::Bit {
-        what: MemoryLocation::Pointer(cpu.registers.get(HL)),
+        what: Operand::Indirect(HL),
         bit: Three,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert!(!cpu.registers.f.is_set(Z));
 }
 
@@ -875,10 +1065,10 @@ fn test_res() {
     cpu.registers.set(A, Value::EightBit(0b0110_0110));
 
     let instruction = Instruction::Res {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         bit: Six,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b0010_0110));
 
     // -- // -- // -- // -- //
@@ -888,10 +1078,10 @@ fn test_res() {
     cpu.registers.set(HL, Value::SixteenBit(0xFACE));
     cpu.write(Value::SixteenBit(0xFACE), Value::EightBit(0b1110_1011));
     let instruction = Instruction::Res {
-        what: MemoryLocation::Pointer(cpu.registers.get(HL)),
+        what: Operand::Indirect(HL),
         bit: Three,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(
         cpu.read(Value::SixteenBit(0xFACE), false),
         Value::EightBit(0b1110_0011)
@@ -904,10 +1094,10 @@ fn test_set() {
     cpu.registers.set(A, Value::EightBit(0b0010_0110));
 
     let instruction = Instruction::Set {
-        what: MemoryLocation::Register(A),
+        what: Operand::Register(A),
         bit: Six,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b0110_0110));
 
     // -- // -- // -- // -- //
@@ -917,10 +1107,10 @@ fn test_set() {
     cpu.registers.set(HL, Value::SixteenBit(0xFACE));
     cpu.write(Value::SixteenBit(0xFACE), Value::EightBit(0b1110_0011));
     let instruction = Instruction::Set {
-        what: MemoryLocation::Pointer(cpu.registers.get(HL)),
+        what: Operand::Indirect(HL),
         bit: Three,
     };
-    cpu.execute(instruction);
+    cpu.execute(instruction).unwrap();
     assert_eq!(
         cpu.read(Value::SixteenBit(0xFACE), false),
         Value::EightBit(0b1110_1011)
@@ -0,0 +1,185 @@
+use yabge::cpu::interrupt::Interrupt;
+use yabge::cpu::registers::Register::{PC, SP};
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+#[test]
+fn test_request_interrupt() {
+    let mut cpu = CPU::default();
+    // Unused bits of IF read as 1
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xFF0F), false),
+        Value::EightBit(0xE0)
+    );
+
+    cpu.request_interrupt(Interrupt::Timer);
+    cpu.request_interrupt(Interrupt::Joypad);
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xFF0F), false),
+        Value::EightBit(0xF4)
+    );
+}
+
+#[test]
+fn test_pending_interrupt_requires_ie() {
+    let mut cpu = CPU::default();
+    cpu.request_interrupt(Interrupt::Serial);
+    assert_eq!(cpu.pending_interrupt(), None);
+
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x08));
+    assert_eq!(cpu.pending_interrupt(), Some(Interrupt::Serial));
+}
+
+#[test]
+fn test_interrupt_not_serviced_without_ime() {
+    let mut cpu = CPU::default();
+    cpu.registers.set(PC, Value::SixteenBit(0x1234));
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x1F));
+    cpu.request_interrupt(Interrupt::VBlank);
+
+    assert_eq!(cpu.handle_interrupts(), None);
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x1234));
+    assert_eq!(cpu.pending_interrupt(), Some(Interrupt::VBlank));
+}
+
+#[test]
+fn test_interrupt_dispatch() {
+    let mut cpu = CPU::default();
+    cpu.set_ime();
+    cpu.registers.set(PC, Value::SixteenBit(0x1234));
+    cpu.registers.set(SP, Value::SixteenBit(0xDFFE));
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x1F));
+    cpu.request_interrupt(Interrupt::Timer);
+
+    let clock_before = cpu.clock();
+    assert_eq!(cpu.handle_interrupts(), Some(Interrupt::Timer));
+
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0050));
+    assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0xDFFC));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xDFFC), true),
+        Value::SixteenBit(0x1234)
+    );
+    assert_eq!(cpu.clock(), clock_before + 5);
+    assert!(!cpu.ime());
+    // The serviced IF bit is cleared
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xFF0F), false),
+        Value::EightBit(0xE0)
+    );
+}
+
+#[test]
+fn test_interrupt_priority() {
+    let expected = [
+        (Interrupt::VBlank, 0x0040),
+        (Interrupt::LcdStat, 0x0048),
+        (Interrupt::Timer, 0x0050),
+        (Interrupt::Serial, 0x0058),
+        (Interrupt::Joypad, 0x0060),
+    ];
+
+    let mut cpu = CPU::default();
+    cpu.registers.set(SP, Value::SixteenBit(0xDFFE));
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x1F));
+    cpu.write(Value::SixteenBit(0xFF0F), Value::EightBit(0x1F));
+
+    for (interrupt, vector) in expected {
+        cpu.set_ime();
+        assert_eq!(cpu.handle_interrupts(), Some(interrupt));
+        assert_eq!(cpu.registers.get(PC), Value::SixteenBit(vector));
+    }
+    cpu.set_ime();
+    assert_eq!(cpu.handle_interrupts(), None);
+}
+
+#[test]
+fn test_disabled_interrupt_is_skipped() {
+    let mut cpu = CPU::default();
+    cpu.set_ime();
+    cpu.registers.set(SP, Value::SixteenBit(0xDFFE));
+    // VBlank is requested but only Joypad is enabled
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x10));
+    cpu.write(Value::SixteenBit(0xFF0F), Value::EightBit(0x11));
+
+    assert_eq!(cpu.handle_interrupts(), Some(Interrupt::Joypad));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0060));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xFF0F), false),
+        Value::EightBit(0xE1)
+    );
+}
+
+#[test]
+fn test_halt_then_service() {
+    let mut cpu = CPU::default();
+    cpu.set_ime();
+    cpu.registers.set(SP, Value::SixteenBit(0xDFFE));
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x76)); // HALT
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x01));
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+    assert!(cpu.halted());
+
+    cpu.request_interrupt(Interrupt::VBlank);
+    cpu.tick_halted();
+    assert!(!cpu.halted());
+    assert_eq!(cpu.handle_interrupts(), Some(Interrupt::VBlank));
+
+    // The return address is the instruction following HALT
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0040));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xDFFC), true),
+        Value::SixteenBit(0x0001)
+    );
+}
+
+// Puts `program` at 0xC000 with a VBlank interrupt already pending
+fn pending_with_program(program: &[u8]) -> CPU {
+    let mut cpu = CPU::default();
+    for (offset, byte) in program.iter().enumerate() {
+        cpu.write(
+            Value::SixteenBit(0xC000 + offset as u16),
+            Value::EightBit(*byte),
+        );
+    }
+    cpu.registers.set(PC, Value::SixteenBit(0xC000));
+    cpu.registers.set(SP, Value::SixteenBit(0xDFFE));
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x1F));
+    cpu.request_interrupt(Interrupt::VBlank);
+    cpu
+}
+
+#[test]
+fn test_ei_waits_for_the_next_instruction() {
+    // EI, NOP, NOP
+    let mut cpu = pending_with_program(&[0xFB, 0x00, 0x00]);
+    cpu.step().unwrap();
+    assert!(!cpu.ime());
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0xC002));
+    assert!(cpu.ime());
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0040));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xDFFC), true),
+        Value::SixteenBit(0xC002)
+    );
+}
+
+#[test]
+fn test_ei_then_di_stays_masked() {
+    // EI, DI, NOP, NOP
+    let mut cpu = pending_with_program(&[0xFB, 0xF3, 0x00, 0x00]);
+    for _ in 0..4 {
+        cpu.step().unwrap();
+        assert_ne!(cpu.registers.get(PC), Value::SixteenBit(0x0040));
+    }
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0xC004));
+    assert!(!cpu.ime());
+}
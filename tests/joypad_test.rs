@@ -0,0 +1,122 @@
+use yabge::cpu::interrupt::Interrupt;
+use yabge::cpu::joypad::Button;
+use yabge::cpu::registers::Register::PC;
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+mod common;
+
+use common::{read, write};
+
+fn joypad_requested(cpu: &CPU) -> bool {
+    read(cpu, 0xFF0F) & Interrupt::Joypad.bit() != 0
+}
+
+#[test]
+fn test_nothing_pressed() {
+    let mut cpu = CPU::default();
+    assert_eq!(read(&cpu, 0xFF00), 0xCF);
+    write(&mut cpu, 0xFF00, 0x30);
+    assert_eq!(read(&cpu, 0xFF00), 0xFF);
+}
+
+#[test]
+fn test_matrix_select() {
+    let mut cpu = CPU::default();
+    cpu.press(Button::Down);
+    cpu.press(Button::A);
+    cpu.press(Button::Start);
+
+    // P14 low reads the d-pad
+    write(&mut cpu, 0xFF00, 0x20);
+    assert_eq!(read(&cpu, 0xFF00), 0xE7);
+    // P15 low reads the buttons
+    write(&mut cpu, 0xFF00, 0x10);
+    assert_eq!(read(&cpu, 0xFF00), 0xD6);
+    // Both low ANDs them together
+    write(&mut cpu, 0xFF00, 0x00);
+    assert_eq!(read(&cpu, 0xFF00), 0xC6);
+    // Neither, and nothing reads as pressed
+    write(&mut cpu, 0xFF00, 0x30);
+    assert_eq!(read(&cpu, 0xFF00), 0xFF);
+
+    cpu.release(Button::Start);
+    write(&mut cpu, 0xFF00, 0x10);
+    assert_eq!(read(&cpu, 0xFF00), 0xDE);
+}
+
+#[test]
+fn test_input_lines_are_read_only() {
+    let mut cpu = CPU::default();
+    // Zeroes in the low nibble don't press anything
+    write(&mut cpu, 0xFF00, 0x20);
+    assert_eq!(read(&cpu, 0xFF00) & 0x0F, 0x0F);
+}
+
+#[test]
+fn test_set_state() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF00, 0x20);
+    cpu.set_joypad_state(Button::Left.bit() | Button::Up.bit() | Button::B.bit());
+    assert_eq!(read(&cpu, 0xFF00), 0xE9);
+    write(&mut cpu, 0xFF00, 0x10);
+    assert_eq!(read(&cpu, 0xFF00), 0xDD);
+
+    cpu.set_joypad_state(0x00);
+    assert_eq!(read(&cpu, 0xFF00), 0xDF);
+}
+
+#[test]
+fn test_interrupt_on_press() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF00, 0x20);
+
+    // A button on the half that isn't selected doesn't pull a line low
+    cpu.press(Button::A);
+    assert!(!joypad_requested(&cpu));
+
+    cpu.press(Button::Right);
+    assert!(joypad_requested(&cpu));
+
+    // Releasing is a low-to-high transition
+    write(&mut cpu, 0xFF0F, 0x00);
+    cpu.release(Button::Right);
+    assert!(!joypad_requested(&cpu));
+
+    // Selecting the half with A held does pull one low
+    write(&mut cpu, 0xFF00, 0x10);
+    assert!(joypad_requested(&cpu));
+}
+
+#[test]
+fn test_interrupt_only_on_a_new_low_line() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF00, 0x10);
+    cpu.press(Button::A);
+    write(&mut cpu, 0xFF0F, 0x00);
+
+    // P10 is already low, so Right shares the line and nothing changes
+    write(&mut cpu, 0xFF00, 0x00);
+    cpu.press(Button::Right);
+    assert!(!joypad_requested(&cpu));
+}
+
+#[test]
+fn test_press_wakes_from_stop() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0x0000, 0x10); // STOP
+    write(&mut cpu, 0x0001, 0x00);
+    write(&mut cpu, 0xFF00, 0x10);
+    cpu.step().unwrap();
+    assert!(cpu.stopped());
+
+    // Not selected, so it stays stopped
+    cpu.press(Button::Up);
+    assert_eq!(cpu.step().unwrap(), 0);
+    assert!(cpu.stopped());
+
+    cpu.press(Button::Start);
+    cpu.step().unwrap();
+    assert!(!cpu.stopped());
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0003));
+}
@@ -0,0 +1,123 @@
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+mod common;
+
+use common::{read, write};
+
+#[test]
+fn test_echo_ram_mirrors_work_ram() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xC000, 0x12);
+    write(&mut cpu, 0xD123, 0x34);
+    write(&mut cpu, 0xDDFF, 0x56);
+    assert_eq!(read(&cpu, 0xE000), 0x12);
+    assert_eq!(read(&cpu, 0xF123), 0x34);
+    assert_eq!(read(&cpu, 0xFDFF), 0x56);
+
+    write(&mut cpu, 0xE001, 0x78);
+    write(&mut cpu, 0xFDFE, 0x9A);
+    assert_eq!(read(&cpu, 0xC001), 0x78);
+    assert_eq!(read(&cpu, 0xDDFE), 0x9A);
+}
+
+#[test]
+fn test_echo_ram_sixteen_bit() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0xE100), Value::SixteenBit(0xBEEF));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC100), true),
+        Value::SixteenBit(0xBEEF)
+    );
+}
+
+#[test]
+fn test_echo_ram_stops_before_oam() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFE00, 0x11);
+    assert_eq!(read(&cpu, 0xDE00), 0x00);
+    write(&mut cpu, 0xDE00, 0x22);
+    assert_eq!(read(&cpu, 0xFE00), 0x11);
+}
+
+#[test]
+fn test_unusable_region() {
+    let mut cpu = CPU::default();
+    for address in 0xFEA0..=0xFEFF {
+        write(&mut cpu, address, 0xFF);
+    }
+    for address in 0xFEA0..=0xFEFF {
+        assert_eq!(read(&cpu, address), 0x00);
+    }
+    // Neighbours are untouched
+    assert_eq!(read(&cpu, 0xFE9F), 0x00);
+    assert_eq!(read(&cpu, 0xFF80), 0x00);
+}
+
+#[test]
+fn test_unused_io_bits_read_as_one() {
+    let mut cpu = CPU::default();
+    // Keep LY=LYC from showing up in STAT
+    write(&mut cpu, 0xFF45, 0x01);
+    for address in [0xFF02, 0xFF07, 0xFF0F, 0xFF41, 0xFF26] {
+        write(&mut cpu, address, 0x00);
+    }
+    assert_eq!(read(&cpu, 0xFF02), 0x7E);
+    assert_eq!(read(&cpu, 0xFF07), 0xF8);
+    assert_eq!(read(&cpu, 0xFF0F), 0xE0);
+    assert_eq!(read(&cpu, 0xFF41), 0x80);
+    assert_eq!(read(&cpu, 0xFF26), 0x70);
+
+    // Write-only sound bits too
+    write(&mut cpu, 0xFF11, 0x80);
+    assert_eq!(read(&cpu, 0xFF11), 0xBF);
+    write(&mut cpu, 0xFF13, 0x12);
+    assert_eq!(read(&cpu, 0xFF13), 0xFF);
+}
+
+#[test]
+fn test_unmapped_io_registers() {
+    let mut cpu = CPU::default();
+    for address in [0xFF03, 0xFF08, 0xFF15, 0xFF27, 0xFF4C, 0xFF7F] {
+        write(&mut cpu, address, 0x00);
+        assert_eq!(read(&cpu, address), 0xFF);
+    }
+}
+
+#[test]
+fn test_read_only_io_bits() {
+    let mut cpu = CPU::default();
+    // LY belongs to the PPU
+    write(&mut cpu, 0xFF44, 0x12);
+    assert_eq!(read(&cpu, 0xFF44), 0x00);
+
+    // The STAT mode and coincidence bits can't be written either
+    write(&mut cpu, 0xFF45, 0x01);
+    write(&mut cpu, 0xFF41, 0xFF);
+    assert_eq!(read(&cpu, 0xFF41), 0xF8);
+
+    // Only the power bit of NR52
+    write(&mut cpu, 0xFF26, 0x8F);
+    assert_eq!(read(&cpu, 0xFF26), 0xF0);
+}
+
+#[test]
+fn test_div_write_resets() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF04, 0xAB);
+    assert_eq!(read(&cpu, 0xFF04), 0x00);
+}
+
+#[test]
+fn test_plain_io_registers() {
+    let mut cpu = CPU::default();
+    for (address, data) in [
+        (0xFF01, 0x5A),
+        (0xFF06, 0xC3),
+        (0xFF47, 0xE4),
+        (0xFF3F, 0x77),
+    ] {
+        write(&mut cpu, address, data);
+        assert_eq!(read(&cpu, address), data);
+    }
+}
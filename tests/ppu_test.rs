@@ -0,0 +1,544 @@
+use yabge::cpu::CPU;
+use yabge::ppu::frame::{Frame, HEIGHT, WIDTH};
+use yabge::ppu::Renderer;
+
+mod common;
+
+use common::{read, write};
+
+// Fills a tile with a single color index
+fn solid_tile(cpu: &mut CPU, address: u16, color: u8) {
+    for row in 0..8 {
+        write(
+            cpu,
+            address + row * 2,
+            if color & 1 != 0 { 0xFF } else { 0x00 },
+        );
+        write(
+            cpu,
+            address + row * 2 + 1,
+            if color & 2 != 0 { 0xFF } else { 0x00 },
+        );
+    }
+}
+
+// Runs the PPU, but not the CPU, until the next frame is done
+fn run_frame(cpu: &mut CPU) {
+    let frames = cpu.frame_count();
+    while cpu.frame_count() == frames {
+        cpu.inc_clock(1);
+    }
+}
+
+// Goes through a PGM the way a reference image on disk would
+fn reference(shade: impl Fn(usize, usize) -> u8) -> Frame {
+    let mut pgm = format!("P5\n{} {}\n255\n", WIDTH, HEIGHT).into_bytes();
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            pgm.push([255, 170, 85, 0][shade(x, y) as usize]);
+        }
+    }
+    Frame::from_pgm(&pgm).unwrap()
+}
+
+// Tile 1 is black, tile 2 light gray, and the 0x9800 map a checkerboard of
+// tiles 0 and 1
+fn checkerboard(cpu: &mut CPU) {
+    solid_tile(cpu, 0x8010, 3);
+    solid_tile(cpu, 0x8020, 1);
+    for index in 0..0x400 {
+        let tile = ((index % 32) + (index / 32)) % 2;
+        write(cpu, 0x9800 + index, tile as u8);
+    }
+    write(cpu, 0xFF47, 0xE4);
+}
+
+fn checker(x: usize, y: usize) -> u8 {
+    if (x / 8 + y / 8) % 2 == 1 {
+        3
+    } else {
+        0
+    }
+}
+
+#[test]
+fn test_background() {
+    let mut cpu = CPU::default();
+    checkerboard(&mut cpu);
+    write(&mut cpu, 0xFF40, 0x91);
+    run_frame(&mut cpu);
+    assert_eq!(*cpu.frame(), reference(checker));
+}
+
+#[test]
+fn test_scroll() {
+    let mut cpu = CPU::default();
+    checkerboard(&mut cpu);
+    write(&mut cpu, 0xFF42, 3);
+    write(&mut cpu, 0xFF43, 250);
+    write(&mut cpu, 0xFF40, 0x91);
+    run_frame(&mut cpu);
+    // The map is 256 pixels across, and wraps
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| checker((x + 250) % 256, y + 3))
+    );
+}
+
+#[test]
+fn test_palette() {
+    let mut cpu = CPU::default();
+    checkerboard(&mut cpu);
+    write(&mut cpu, 0xFF47, 0x1B);
+    write(&mut cpu, 0xFF40, 0x91);
+    run_frame(&mut cpu);
+    assert_eq!(*cpu.frame(), reference(|x, y| 3 - checker(x, y)));
+}
+
+#[test]
+fn test_signed_tile_data() {
+    let mut cpu = CPU::default();
+    // With LCDC bit 4 clear, tile 0 is at 0x9000 and tile 0x80 at 0x8800
+    solid_tile(&mut cpu, 0x9000, 2);
+    solid_tile(&mut cpu, 0x8800, 3);
+    solid_tile(&mut cpu, 0x8000, 1);
+    for index in 0..0x400 {
+        write(
+            &mut cpu,
+            0x9800 + index,
+            if index % 2 == 0 { 0x00 } else { 0x80 },
+        );
+    }
+    write(&mut cpu, 0xFF47, 0xE4);
+    write(&mut cpu, 0xFF40, 0x81);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, _| if (x / 8) % 2 == 0 { 2 } else { 3 })
+    );
+}
+
+#[test]
+fn test_window() {
+    let mut cpu = CPU::default();
+    checkerboard(&mut cpu);
+    // The 0x9C00 map is all light gray
+    for index in 0..0x400 {
+        write(&mut cpu, 0x9C00 + index, 2);
+    }
+    write(&mut cpu, 0xFF4A, 100);
+    write(&mut cpu, 0xFF4B, 7 + 60);
+    write(&mut cpu, 0xFF40, 0xF1);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| if x >= 60 && y >= 100 {
+            1
+        } else {
+            checker(x, y)
+        })
+    );
+}
+
+#[test]
+fn test_window_has_its_own_line_counter() {
+    let mut cpu = CPU::default();
+    // Window tile rows alternate black and white
+    solid_tile(&mut cpu, 0x8010, 3);
+    for index in 0..0x400 {
+        write(&mut cpu, 0x9C00 + index, ((index / 32) % 2) as u8);
+    }
+    write(&mut cpu, 0xFF47, 0xE4);
+    write(&mut cpu, 0xFF4A, 4);
+    write(&mut cpu, 0xFF4B, 7);
+    write(&mut cpu, 0xFF40, 0xF1);
+    run_frame(&mut cpu);
+    // Starts from the window's first line no matter where WY puts it
+    assert_eq!(
+        *cpu.frame(),
+        reference(|_, y| if y >= 4 && (y - 4) / 8 % 2 == 1 { 3 } else { 0 })
+    );
+}
+
+#[test]
+fn test_background_disabled() {
+    let mut cpu = CPU::default();
+    checkerboard(&mut cpu);
+    write(&mut cpu, 0xFF40, 0x90);
+    run_frame(&mut cpu);
+    assert_eq!(*cpu.frame(), reference(|_, _| 0));
+}
+
+#[test]
+fn test_frame_timing() {
+    let mut cpu = CPU::default();
+    // Nothing happens with the LCD off
+    for _ in 0..20000 {
+        cpu.inc_clock(1);
+    }
+    assert_eq!(cpu.frame_count(), 0);
+
+    write(&mut cpu, 0xFF40, 0x91);
+    run_frame(&mut cpu);
+    // 154 lines of 456 dots, 4 dots to an M-cycle
+    let start = cpu.clock();
+    run_frame(&mut cpu);
+    assert_eq!(cpu.clock() - start, 154 * 456 / 4);
+}
+
+#[test]
+fn test_pgm_round_trip() {
+    let frame = reference(|x, y| ((x + y) % 4) as u8);
+    let pgm = frame.to_pgm();
+    assert!(pgm.starts_with(b"P5\n160 144\n3\n"));
+    assert_eq!(Frame::from_pgm(&pgm), Some(frame));
+    assert_eq!(Frame::from_pgm(b"P5\n1 1\n3\n\x00"), None);
+}
+
+// Writes OAM entry `index`, at a screen position rather than the raw one
+fn object(cpu: &mut CPU, index: u16, x: i16, y: i16, tile: u8, flags: u8) {
+    let entry = 0xFE00 + index * 4;
+    write(cpu, entry, (y + 16) as u8);
+    write(cpu, entry + 1, (x + 8) as u8);
+    write(cpu, entry + 2, tile);
+    write(cpu, entry + 3, flags);
+}
+
+// A white background, black tile 1, light gray tile 2 and tile 3 with only
+// its top left pixel set
+fn object_tiles(cpu: &mut CPU) {
+    solid_tile(cpu, 0x8010, 3);
+    solid_tile(cpu, 0x8020, 1);
+    write(cpu, 0x8030, 0x80);
+    write(cpu, 0x8031, 0x80);
+    write(cpu, 0xFF47, 0xE4);
+    write(cpu, 0xFF48, 0xE4);
+}
+
+fn square(x: usize, y: usize, left: usize, top: usize, size: usize) -> bool {
+    (left..left + size).contains(&x) && (top..top + size).contains(&y)
+}
+
+#[test]
+fn test_objects() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    object(&mut cpu, 0, 20, 30, 1, 0x00);
+    // Partly off the top left corner
+    object(&mut cpu, 1, -4, -4, 1, 0x00);
+    write(&mut cpu, 0xFF40, 0x93);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| if square(x, y, 20, 30, 8) || (x < 4 && y < 4) {
+            3
+        } else {
+            0
+        })
+    );
+}
+
+#[test]
+fn test_objects_disabled() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    object(&mut cpu, 0, 20, 30, 1, 0x00);
+    write(&mut cpu, 0xFF40, 0x91);
+    run_frame(&mut cpu);
+    assert_eq!(*cpu.frame(), reference(|_, _| 0));
+}
+
+#[test]
+fn test_object_flips() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    object(&mut cpu, 0, 0, 0, 3, 0x00);
+    object(&mut cpu, 1, 10, 0, 3, 0x20);
+    object(&mut cpu, 2, 20, 0, 3, 0x40);
+    object(&mut cpu, 3, 30, 0, 3, 0x60);
+    write(&mut cpu, 0xFF40, 0x93);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| match (x, y) {
+            (0, 0) | (17, 0) | (20, 7) | (37, 7) => 3,
+            _ => 0,
+        })
+    );
+}
+
+#[test]
+fn test_object_palettes() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    write(&mut cpu, 0xFF49, 0x1B);
+    object(&mut cpu, 0, 0, 0, 2, 0x00);
+    object(&mut cpu, 1, 8, 0, 2, 0x10);
+    write(&mut cpu, 0xFF40, 0x93);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| match (x, y) {
+            (0..=7, 0..=7) => 1,
+            (8..=15, 0..=7) => 2,
+            _ => 0,
+        })
+    );
+}
+
+#[test]
+fn test_tall_objects() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    solid_tile(&mut cpu, 0x8040, 3);
+    solid_tile(&mut cpu, 0x8050, 1);
+    // The low bit of the tile number is ignored, so this is tiles 4 and 5
+    object(&mut cpu, 0, 0, 0, 5, 0x00);
+    // Flipping swaps the halves
+    object(&mut cpu, 1, 8, 0, 4, 0x40);
+    write(&mut cpu, 0xFF40, 0x97);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| match (x, y) {
+            (0..=7, 0..=7) | (8..=15, 8..=15) => 3,
+            (0..=7, 8..=15) | (8..=15, 0..=7) => 1,
+            _ => 0,
+        })
+    );
+}
+
+#[test]
+fn test_object_height_change_mid_line() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    // Tiles 0 and 1, flipped so the black one is on top
+    object(&mut cpu, 0, 0, 0, 1, 0x40);
+    write(&mut cpu, 0xFF40, 0x86);
+    while read(&cpu, 0xFF44) != 12 || read(&cpu, 0xFF41) & 0x03 != 3 {
+        cpu.inc_clock(1);
+    }
+    // Line 12 was picked up as part of an 8x16 object, but gets drawn from
+    // row 4 of an 8x8 one
+    write(&mut cpu, 0xFF40, 0x82);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| if x < 8 && (y < 8 || y == 12) { 3 } else { 0 })
+    );
+}
+
+#[test]
+fn test_object_behind_background() {
+    let mut cpu = CPU::default();
+    checkerboard(&mut cpu);
+    solid_tile(&mut cpu, 0x8020, 1);
+    write(&mut cpu, 0xFF48, 0xE4);
+    // Covers a black and a white square of the checkerboard
+    object(&mut cpu, 0, 4, 0, 2, 0x80);
+    object(&mut cpu, 1, 4, 8, 2, 0x00);
+    write(&mut cpu, 0xFF40, 0x93);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| match (x, y) {
+            // Only background color 0 lets it through
+            (4..=7, 0..=7) => 1,
+            (4..=11, 8..=15) => 1,
+            _ => checker(x, y),
+        })
+    );
+}
+
+#[test]
+fn test_object_x_priority() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    // Further left wins, even when later in OAM
+    object(&mut cpu, 0, 4, 0, 2, 0x00);
+    object(&mut cpu, 1, 0, 0, 1, 0x00);
+    // At the same X, the first in OAM wins
+    object(&mut cpu, 2, 20, 0, 2, 0x00);
+    object(&mut cpu, 3, 20, 0, 1, 0x00);
+    write(&mut cpu, 0xFF40, 0x93);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| match (x, y) {
+            (0..=7, 0..=7) => 3,
+            (8..=11, 0..=7) => 1,
+            (20..=27, 0..=7) => 1,
+            _ => 0,
+        })
+    );
+}
+
+#[test]
+fn test_transparent_object_pixels() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    // The single dot wins where it's set, and lets the square below show
+    // everywhere else
+    object(&mut cpu, 0, 0, 0, 3, 0x00);
+    object(&mut cpu, 1, 0, 0, 2, 0x00);
+    write(&mut cpu, 0xFF40, 0x93);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| match (x, y) {
+            (0, 0) => 3,
+            (0..=7, 0..=7) => 1,
+            _ => 0,
+        })
+    );
+}
+
+#[test]
+fn test_ten_objects_per_line() {
+    let mut cpu = CPU::default();
+    object_tiles(&mut cpu);
+    for index in 0..11 {
+        object(&mut cpu, index, index as i16 * 10, 0, 1, 0x00);
+    }
+    // Off screen objects still use up a slot
+    for index in 0..10 {
+        object(&mut cpu, 20 + index, -8, 20, 1, 0x00);
+    }
+    object(&mut cpu, 30, 0, 20, 1, 0x00);
+    write(&mut cpu, 0xFF40, 0x93);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| if y < 8 && x < 100 && x % 10 < 8 { 3 } else { 0 })
+    );
+}
+
+// Draws a scene with each renderer, which should come out the same
+fn assert_renderers_agree(setup: impl Fn(&mut CPU)) {
+    let frames: Vec<Frame> = [Renderer::Scanline, Renderer::Fifo]
+        .into_iter()
+        .map(|renderer| {
+            let mut cpu = CPU::new(renderer);
+            setup(&mut cpu);
+            run_frame(&mut cpu);
+            cpu.frame().clone()
+        })
+        .collect();
+    assert_eq!(frames[0], frames[1]);
+}
+
+#[test]
+fn test_fifo_matches_scanline() {
+    assert_renderers_agree(|cpu| {
+        checkerboard(cpu);
+        write(cpu, 0xFF42, 3);
+        write(cpu, 0xFF43, 250);
+        write(cpu, 0xFF40, 0x91);
+    });
+
+    // Signed tile data, and the window both on screen and hanging off the
+    // left edge
+    for wx in [3, 7 + 60] {
+        assert_renderers_agree(|cpu| {
+            checkerboard(cpu);
+            for index in 0..0x400 {
+                write(cpu, 0x9C00 + index, ((index % 3) * 0x80) as u8);
+            }
+            solid_tile(cpu, 0x9000, 2);
+            write(cpu, 0xFF43, 5);
+            write(cpu, 0xFF4A, 30);
+            write(cpu, 0xFF4B, wx);
+            write(cpu, 0xFF40, 0xE1);
+        });
+    }
+
+    // Objects over a scrolled background, with every flag and more than
+    // fit on a line
+    for (lcdc, scx) in [(0x93, 0), (0x97, 3), (0x92, 6)] {
+        assert_renderers_agree(|cpu| {
+            checkerboard(cpu);
+            write(cpu, 0x8030, 0x81);
+            write(cpu, 0x8031, 0xC0);
+            write(cpu, 0xFF43, scx);
+            write(cpu, 0xFF48, 0xE4);
+            write(cpu, 0xFF49, 0x1B);
+            for index in 0..16u16 {
+                let x = index as i16 * 9 - 4;
+                let y = index as i16 % 3 * 5 - 2;
+                object(
+                    cpu,
+                    index,
+                    x,
+                    y,
+                    2 + index as u8 % 2,
+                    (index as u8 * 0x10) & 0xF0,
+                );
+            }
+            write(cpu, 0xFF40, lcdc);
+        });
+    }
+}
+
+#[test]
+fn test_fifo_mid_line_palette() {
+    let mut cpu = CPU::new(Renderer::Fifo);
+    solid_tile(&mut cpu, 0x8000, 3);
+    write(&mut cpu, 0xFF47, 0xE4);
+    write(&mut cpu, 0xFF40, 0x91);
+    while read(&cpu, 0xFF44) != 10 || read(&cpu, 0xFF41) & 0x03 != 3 {
+        cpu.inc_clock(1);
+    }
+    // One dot into pixel transfer, and it takes 12 more before the first
+    // pixel goes out, so 81 dots later 69 pixels are done
+    for _ in 0..20 {
+        cpu.inc_clock(1);
+    }
+    write(&mut cpu, 0xFF47, 0x00);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| if y < 10 || y == 10 && x < 69 { 3 } else { 0 })
+    );
+}
+
+#[test]
+fn test_fifo_object_height_change_mid_line() {
+    let mut cpu = CPU::new(Renderer::Fifo);
+    object_tiles(&mut cpu);
+    object(&mut cpu, 0, 0, 0, 1, 0x40);
+    write(&mut cpu, 0xFF40, 0x86);
+    while read(&cpu, 0xFF44) != 12 || read(&cpu, 0xFF41) & 0x03 != 3 {
+        cpu.inc_clock(1);
+    }
+    // Before the object gets fetched, so it's drawn 8x8 from row 4
+    write(&mut cpu, 0xFF40, 0x82);
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| if x < 8 && (y < 8 || y == 12) { 3 } else { 0 })
+    );
+}
+
+#[test]
+fn test_fifo_window_needs_wy_match() {
+    let mut cpu = CPU::new(Renderer::Fifo);
+    checkerboard(&mut cpu);
+    for index in 0..0x400 {
+        write(&mut cpu, 0x9C00 + index, 2);
+    }
+    write(&mut cpu, 0xFF4A, 200);
+    write(&mut cpu, 0xFF4B, 7);
+    write(&mut cpu, 0xFF40, 0xF1);
+    while read(&cpu, 0xFF44) != 50 {
+        cpu.inc_clock(1);
+    }
+    // LY has already gone past, so the window waits for the next frame
+    write(&mut cpu, 0xFF4A, 20);
+    run_frame(&mut cpu);
+    assert_eq!(*cpu.frame(), reference(checker));
+    run_frame(&mut cpu);
+    assert_eq!(
+        *cpu.frame(),
+        reference(|x, y| if y >= 20 { 1 } else { checker(x, y) })
+    );
+}
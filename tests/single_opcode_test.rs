@@ -1,5 +1,8 @@
+use yabge::cartridge::header::header_checksum;
+use yabge::cpu::error::EmulatorError;
 use yabge::cpu::flag::Flag;
 use yabge::cpu::flag::Flag::Z;
+use yabge::cpu::joypad::Button;
 use yabge::cpu::registers::Register;
 use yabge::cpu::registers::Register::{A, B, BC, C, DE, HL, L, PC, SP};
 use yabge::cpu::value::Value;
@@ -15,7 +18,7 @@ fn test_0x01() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(BC), Value::SixteenBit(0xABCD));
@@ -32,7 +35,7 @@ fn test_0x02() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -51,7 +54,7 @@ fn test_0x03() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(BC), Value::SixteenBit(0x1235));
@@ -67,7 +70,7 @@ fn test_0x06() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(B), Value::EightBit(0xAB));
@@ -83,7 +86,7 @@ fn test_0x07() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b0010_0101));
@@ -102,7 +105,7 @@ fn test_0x08() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -130,7 +133,7 @@ fn test_0x09() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0x4422));
@@ -148,7 +151,7 @@ fn test_0x0a() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0xFF));
@@ -164,7 +167,7 @@ fn test_0x0b() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(BC), Value::SixteenBit(0x1233));
@@ -180,7 +183,7 @@ fn test_0x0c() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(C), Value::EightBit(0x13));
@@ -196,13 +199,101 @@ fn test_0x0f() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1100_1001));
     assert!(cpu.registers.f.is_set(Flag::C));
 }
 
+#[test]
+fn test_0x10() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x10)); // STOP
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x00));
+    cpu.write(Value::SixteenBit(0xFF04), Value::EightBit(0xAB)); // DIV
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+
+    assert!(cpu.stopped());
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0002));
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xFF04), false),
+        Value::EightBit(0x00)
+    );
+
+    // Nothing pressed: the CPU stays stopped and the clock does not move.
+    let clock_before = cpu.clock();
+    cpu.tick_stopped();
+    assert!(cpu.stopped());
+    assert_eq!(cpu.clock(), clock_before);
+
+    // P10 goes low
+    cpu.press(Button::Right);
+    cpu.tick_stopped();
+    assert!(!cpu.stopped());
+}
+
+// A ROM-only cartridge whose header asks for CGB mode
+fn cgb_rom() -> Vec<u8> {
+    let mut rom = vec![0x00; 0x8000];
+    rom[0x0143] = 0x80;
+    rom[0x014D] = header_checksum(&rom);
+    rom
+}
+
+#[test]
+fn test_0x10_speed_switch() {
+    let mut cpu: CPU = Default::default();
+    cpu.load_cartridge(cgb_rom()).unwrap();
+    cpu.registers.set(PC, Value::SixteenBit(0xC000));
+    cpu.write(Value::SixteenBit(0xC000), Value::EightBit(0x10)); // STOP
+    cpu.write(Value::SixteenBit(0xC001), Value::EightBit(0x00));
+    cpu.write(Value::SixteenBit(0xFF4D), Value::EightBit(0x01)); // KEY1: arm the switch
+
+    let val = cpu.read(Value::SixteenBit(0xC000), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+
+    assert!(!cpu.stopped());
+    assert!(cpu.double_speed());
+    // Bits 1-6 are unused and read as 1
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xFF4D), false),
+        Value::EightBit(0xFE)
+    );
+}
+
+#[test]
+fn test_0x10_no_speed_switch_on_dmg() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x10)); // STOP
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x00));
+    cpu.write(Value::SixteenBit(0xFF4D), Value::EightBit(0x01));
+    // There's no KEY1 to arm
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xFF4D), false),
+        Value::EightBit(0xFF)
+    );
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+
+    assert!(cpu.stopped());
+    assert!(!cpu.double_speed());
+    cpu.switch_speed();
+    assert!(!cpu.double_speed());
+}
+
 #[test]
 fn test_0x11() {
     let mut cpu: CPU = Default::default();
@@ -213,7 +304,7 @@ fn test_0x11() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(DE), Value::SixteenBit(0xABCD));
@@ -229,7 +320,7 @@ fn test_0x12() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -248,7 +339,7 @@ fn test_0x17() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b0101_0001));
@@ -267,10 +358,11 @@ fn test_0x18() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
-    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(65451));
+    // Counted from 0x0002, the end of the JR
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(65453));
 }
 
 #[test]
@@ -283,7 +375,7 @@ fn test_0x1f() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1001_0100));
@@ -298,10 +390,10 @@ fn test_0x20() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
-    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x10));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x12));
 
     let mut cpu2: CPU = Default::default();
     cpu2.registers.f.set(Z);
@@ -311,7 +403,7 @@ fn test_0x20() {
     let val = cpu2.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu2.lookup(code);
-        cpu2.execute(inst);
+        cpu2.execute(inst).unwrap();
     }
 
     assert_eq!(cpu2.registers.get(PC), Value::SixteenBit(0x02));
@@ -326,7 +418,7 @@ fn test_0x21() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0xABCD));
@@ -342,7 +434,7 @@ fn test_0x22() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -360,7 +452,7 @@ fn test_0x29() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0x0156));
@@ -376,7 +468,7 @@ fn test_0x2a() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(A), Value::EightBit(0xFE));
@@ -393,12 +485,57 @@ fn test_0x31() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0xFACE));
 }
 
+#[test]
+fn test_0x34() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x34));
+    cpu.write(Value::SixteenBit(0xC123), Value::EightBit(0x41));
+    cpu.registers.set(HL, Value::SixteenBit(0xC123));
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC123), false),
+        Value::EightBit(0x42)
+    );
+    assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0xC123));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0001));
+    assert_eq!(cpu.clock(), 3);
+}
+
+#[test]
+fn test_0x35() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x35));
+    cpu.write(Value::SixteenBit(0xC123), Value::EightBit(0x01));
+    cpu.registers.set(HL, Value::SixteenBit(0xC123));
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+
+    assert_eq!(
+        cpu.read(Value::SixteenBit(0xC123), false),
+        Value::EightBit(0x00)
+    );
+    assert!(cpu.registers.f.is_set(Z));
+    assert!(cpu.registers.f.is_set(Flag::N));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0001));
+    assert_eq!(cpu.clock(), 3);
+}
+
 #[test]
 fn test_0x36() {
     let mut cpu: CPU = Default::default();
@@ -409,7 +546,7 @@ fn test_0x36() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -431,7 +568,7 @@ fn test_0x36() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -452,12 +589,166 @@ fn test_0x37() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert!(cpu.registers.f.is_set(Flag::C));
 }
 
+#[test]
+fn test_0x76_wakes_without_ime() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x76)); // HALT
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x3C)); // INC A
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x04)); // IE = Timer
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+    assert!(cpu.halted());
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0001));
+
+    // Nothing is pending, so the CPU stays halted while the clock runs.
+    let clock_before = cpu.clock();
+    for _ in 0..10 {
+        cpu.tick_halted();
+    }
+    assert!(cpu.halted());
+    assert_eq!(cpu.clock(), clock_before + 10);
+
+    // Requesting the enabled interrupt wakes the CPU even though IME is off.
+    cpu.write(Value::SixteenBit(0xFF0F), Value::EightBit(0x04));
+    cpu.tick_halted();
+    assert!(!cpu.halted());
+
+    let val = cpu.read(cpu.registers.get(PC), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x01));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0002));
+}
+
+#[test]
+fn test_0x76_wakes_with_ime() {
+    let mut cpu: CPU = Default::default();
+    cpu.set_ime();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x76)); // HALT
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x01)); // IE = VBlank
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+    assert!(cpu.halted());
+
+    // An interrupt that is requested but not enabled does not wake the CPU.
+    cpu.write(Value::SixteenBit(0xFF0F), Value::EightBit(0x02));
+    cpu.tick_halted();
+    assert!(cpu.halted());
+
+    cpu.write(Value::SixteenBit(0xFF0F), Value::EightBit(0x03));
+    cpu.tick_halted();
+    assert!(!cpu.halted());
+}
+
+#[test]
+fn test_0x76_halt_bug() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x76)); // HALT
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x3C)); // INC A
+    cpu.write(Value::SixteenBit(0x0002), Value::EightBit(0x00)); // NOP
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x04)); // IE = Timer
+    cpu.write(Value::SixteenBit(0xFF0F), Value::EightBit(0x04)); // IF = Timer
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+    // IME is off with an interrupt already pending: HALT is skipped...
+    assert!(!cpu.halted());
+
+    // ...and the byte after it is executed twice.
+    for _ in 0..2 {
+        let val = cpu.read(cpu.registers.get(PC), false);
+        if let Value::EightBit(code) = val {
+            let inst = cpu.lookup(code);
+            cpu.execute(inst).unwrap();
+        }
+    }
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x02));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0002));
+}
+
+#[test]
+fn test_0x76_halt_bug_repeats_operand() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x76)); // HALT
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x06)); // LD B, d8
+    cpu.write(Value::SixteenBit(0x0002), Value::EightBit(0xAB));
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x01));
+    cpu.write(Value::SixteenBit(0xFF0F), Value::EightBit(0x01));
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+
+    // The opcode byte is read again as the operand: LD B, 0x06.
+    let val = cpu.read(cpu.registers.get(PC), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+    assert_eq!(cpu.registers.get(B), Value::EightBit(0x06));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0002));
+}
+
+#[test]
+fn test_0x8e() {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x8E));
+    cpu.write(Value::SixteenBit(0xC123), Value::EightBit(0x0F));
+    cpu.registers.set(HL, Value::SixteenBit(0xC123));
+    cpu.registers.set(A, Value::EightBit(0x10));
+    cpu.registers.f.set(Flag::C);
+
+    let val = cpu.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x20));
+    assert!(cpu.registers.f.is_set(Flag::H));
+    assert!(!cpu.registers.f.is_set(Flag::C));
+    assert_eq!(cpu.clock(), 2);
+
+    // The carry out of (HL) + C still counts
+    let mut cpu2: CPU = Default::default();
+    cpu2.write(Value::SixteenBit(0x0000), Value::EightBit(0x8E));
+    cpu2.write(Value::SixteenBit(0xC123), Value::EightBit(0xFF));
+    cpu2.registers.set(HL, Value::SixteenBit(0xC123));
+    cpu2.registers.set(A, Value::EightBit(0x00));
+    cpu2.registers.f.set(Flag::C);
+
+    let val = cpu2.read(Value::SixteenBit(0x00), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu2.lookup(code);
+        cpu2.execute(inst).unwrap();
+    }
+
+    assert_eq!(cpu2.registers.get(A), Value::EightBit(0x00));
+    assert!(cpu2.registers.f.is_set(Flag::H));
+    assert!(cpu2.registers.f.is_set(Flag::C));
+}
+
 #[test]
 fn test_0xc2() {
     let mut cpu: CPU = Default::default();
@@ -471,7 +762,7 @@ fn test_0xc2() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0xABCD));
@@ -490,7 +781,7 @@ fn test_0xc4() {
     let val = cpu.read(cpu.registers.get(PC), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x1234));
@@ -516,7 +807,7 @@ fn test_0xe0() {
     let val = cpu.read(cpu.registers.get(PC), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -537,7 +828,7 @@ fn test_0xe2() {
     let val = cpu.read(cpu.registers.get(PC), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -546,6 +837,69 @@ fn test_0xe2() {
     );
 }
 
+/// Runs `opcode` (ADD SP, s8 or LD HL, SP+s8) for every low byte of SP and
+/// every offset, checking the result against `target` and the flags against
+/// the unsigned addition of the low byte of SP and the offset.
+fn check_sp_plus_offset_exhaustively(opcode: u8, target: Register) {
+    let mut cpu: CPU = Default::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(opcode));
+
+    for sp_high in [0x00u16, 0xC0, 0xFF] {
+        for sp_low in 0x00..=0xFFu16 {
+            for offset in 0x00..=0xFFu8 {
+                let sp = sp_high << 8 | sp_low;
+                cpu.registers.set(PC, Value::SixteenBit(0x0000));
+                cpu.registers.set(SP, Value::SixteenBit(sp));
+                // Start with every flag set so we can check they are cleared
+                cpu.registers.set(Register::AF, Value::SixteenBit(0x00F0));
+                cpu.write(Value::SixteenBit(0x0001), Value::EightBit(offset));
+
+                let val = cpu.read(cpu.registers.get(PC), false);
+                if let Value::EightBit(code) = val {
+                    let inst = cpu.lookup(code);
+                    cpu.execute(inst).unwrap();
+                }
+
+                let expected = sp.wrapping_add(offset as i8 as u16);
+                let half_carry = (sp & 0x0F) + (offset as u16 & 0x0F) > 0x0F;
+                let carry = (sp & 0xFF) + offset as u16 > 0xFF;
+                assert_eq!(cpu.registers.get(target), Value::SixteenBit(expected));
+                assert_eq!(cpu.registers.f.is_set(Flag::H), half_carry);
+                assert_eq!(cpu.registers.f.is_set(Flag::C), carry);
+                assert!(!cpu.registers.f.is_set(Flag::Z));
+                assert!(!cpu.registers.f.is_set(Flag::N));
+                assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0002));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_0xe8() {
+    let mut cpu: CPU = Default::default();
+    cpu.registers.set(PC, Value::SixteenBit(0xAABB));
+    cpu.registers.set(SP, Value::SixteenBit(0xCCDD));
+
+    cpu.write(Value::SixteenBit(0xAABB), Value::EightBit(0xE8));
+    cpu.write(Value::SixteenBit(0xAABC), Value::EightBit(0x9C)); // -100i8
+
+    let val = cpu.read(cpu.registers.get(PC), false);
+    if let Value::EightBit(code) = val {
+        let inst = cpu.lookup(code);
+        cpu.execute(inst).unwrap();
+    }
+
+    assert_eq!(cpu.registers.get(SP), Value::SixteenBit(0xCC79));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0xAABD));
+    assert!(cpu.registers.f.is_set(Flag::H));
+    assert!(cpu.registers.f.is_set(Flag::C));
+}
+
+#[test]
+fn test_0xe8_exhaustive() {
+    check_sp_plus_offset_exhaustively(0xE8, SP);
+}
+
 #[test]
 fn test_0xea() {
     let mut cpu: CPU = Default::default();
@@ -558,7 +912,7 @@ fn test_0xea() {
     let val = cpu.read(cpu.registers.get(PC), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -579,7 +933,7 @@ fn test_0xf8() {
     let val = cpu.read(cpu.registers.get(PC), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0xCCDD) - 1u8);
@@ -597,7 +951,7 @@ fn test_0xf8() {
     let val = cpu.read(cpu.registers.get(PC), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0xCCDD) - 100u8);
@@ -615,13 +969,18 @@ fn test_0xf8() {
     let val = cpu.read(cpu.registers.get(PC), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0xCCDD) + 5u8);
     assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0xCCE2));
 }
 
+#[test]
+fn test_0xf8_exhaustive() {
+    check_sp_plus_offset_exhaustively(0xF8, HL);
+}
+
 #[test]
 fn test_0xcb06() {
     let mut cpu: CPU = Default::default();
@@ -634,7 +993,7 @@ fn test_0xcb06() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert_eq!(
         cpu.read(Value::SixteenBit(0xCAFE), false),
@@ -655,7 +1014,7 @@ fn test_0xcb0e() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert_eq!(
         cpu.read(Value::SixteenBit(0xCAFE), false),
@@ -676,7 +1035,7 @@ fn test_0xcb15() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert_eq!(cpu.registers.get(L), Value::EightBit(0b0111_0101));
     assert!(!cpu.registers.f.is_set(Flag::C))
@@ -695,7 +1054,7 @@ fn test_0xcb1e() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert_eq!(
         cpu.read(Value::SixteenBit(0xCAFE), false),
@@ -717,7 +1076,7 @@ fn test_0xcb26() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert_eq!(
         cpu.read(Value::SixteenBit(0xCAFE), false),
@@ -736,7 +1095,7 @@ fn test_0xcb27() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert_eq!(cpu.registers.get(A), Value::EightBit(0b1011_1100));
     assert!(!cpu.registers.f.is_set(Flag::C))
@@ -753,7 +1112,7 @@ fn test_0xcb35() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert_eq!(cpu.registers.get(L), Value::EightBit(0b1110_0101));
 }
@@ -771,7 +1130,7 @@ fn test_0xcb36() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert_eq!(
         cpu.read(Value::SixteenBit(0xCAFE), false),
@@ -790,7 +1149,7 @@ fn test_0xcb45() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert!(cpu.registers.f.is_set(Z));
 }
@@ -807,7 +1166,7 @@ fn test_0xcb46() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert!(!cpu.registers.f.is_set(Z));
 }
@@ -823,7 +1182,7 @@ fn test_0xcb6c() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
     assert!(cpu.registers.f.is_set(Z));
 }
@@ -840,7 +1199,7 @@ fn test_0xcbbe() {
     let val = cpu.read(Value::SixteenBit(0x00), false);
     if let Value::EightBit(code) = val {
         let inst = cpu.lookup(code);
-        cpu.execute(inst);
+        cpu.execute(inst).unwrap();
     }
 
     assert_eq!(
@@ -848,3 +1207,46 @@ fn test_0xcbbe() {
         Value::EightBit(0b0101_1110)
     );
 }
+
+#[test]
+fn test_0x40_breakpoint() {
+    let mut cpu: CPU = Default::default();
+    cpu.registers.set(B, Value::EightBit(0xAB));
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x40)); // LD B, B
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x00)); // NOP
+
+    cpu.step().unwrap();
+    assert!(cpu.breakpoint());
+    assert_eq!(cpu.registers.get(B), Value::EightBit(0xAB));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0001));
+
+    cpu.step().unwrap();
+    assert!(!cpu.breakpoint());
+}
+
+#[test]
+fn test_illegal_opcodes() {
+    for opcode in [
+        0xD3, 0xDB, 0xDD, 0xE3, 0xE4, 0xEB, 0xEC, 0xED, 0xF4, 0xFC, 0xFD,
+    ] {
+        let mut cpu: CPU = Default::default();
+        cpu.registers.set(PC, Value::SixteenBit(0x0150));
+        cpu.write(Value::SixteenBit(0x0150), Value::EightBit(opcode));
+
+        assert_eq!(
+            cpu.step(),
+            Err(EmulatorError::IllegalOpcode { opcode, pc: 0x0150 })
+        );
+        assert!(cpu.locked());
+        assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0150));
+
+        // The CPU never runs again, but the clock keeps ticking
+        let clock_before = cpu.clock();
+        for _ in 0..10 {
+            cpu.step().unwrap();
+        }
+        assert!(cpu.locked());
+        assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0150));
+        assert_eq!(cpu.clock(), clock_before + 10);
+    }
+}
@@ -0,0 +1,213 @@
+use yabge::cpu::interrupt::Interrupt;
+use yabge::cpu::CPU;
+use yabge::ppu::Renderer;
+
+mod common;
+
+use common::{read, tick, write};
+
+fn mode(cpu: &CPU) -> u8 {
+    read(cpu, 0xFF41) & 0x03
+}
+
+// Ticks until the condition holds, returning how many M-cycles it took
+fn wait_for(cpu: &mut CPU, condition: impl Fn(&CPU) -> bool) -> u32 {
+    let mut cycles = 0;
+    while !condition(cpu) {
+        cpu.inc_clock(1);
+        cycles += 1;
+    }
+    cycles
+}
+
+// M-cycles spent in modes 2, 3 and 0 on the next full line
+fn mode_lengths(cpu: &mut CPU) -> [u32; 3] {
+    wait_for(cpu, |cpu| mode(cpu) != 2);
+    wait_for(cpu, |cpu| mode(cpu) == 2);
+    [
+        wait_for(cpu, |cpu| mode(cpu) != 2),
+        wait_for(cpu, |cpu| mode(cpu) != 3),
+        wait_for(cpu, |cpu| mode(cpu) != 0),
+    ]
+}
+
+fn requested(cpu: &CPU, interrupt: Interrupt) -> bool {
+    read(cpu, 0xFF0F) & interrupt.bit() != 0
+}
+
+// STAT interrupts over one whole frame, starting a little way into the
+// first line so nothing at the frame boundary counts twice
+fn count_stat_interrupts(cpu: &mut CPU) -> u32 {
+    tick(cpu, 10);
+    write(cpu, 0xFF0F, 0x00);
+    let mut count = 0;
+    for _ in 0..154 * 114 {
+        cpu.inc_clock(1);
+        if requested(cpu, Interrupt::LcdStat) {
+            count += 1;
+            write(cpu, 0xFF0F, 0x00);
+        }
+    }
+    count
+}
+
+#[test]
+fn test_mode_timing() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF40, 0x91);
+    assert_eq!(mode(&cpu), 2);
+    assert_eq!(mode_lengths(&mut cpu), [20, 43, 51]);
+}
+
+#[test]
+fn test_fine_scroll_stretches_transfer() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF43, 0x04);
+    write(&mut cpu, 0xFF40, 0x91);
+    assert_eq!(mode_lengths(&mut cpu), [20, 44, 50]);
+}
+
+#[test]
+fn test_objects_stretch_transfer() {
+    let mut cpu = CPU::default();
+    // An object at X 0 always costs 11 dots, plus 1 for SCX
+    write(&mut cpu, 0xFE00, 16);
+    write(&mut cpu, 0xFE01, 0);
+    write(&mut cpu, 0xFF43, 0x01);
+    write(&mut cpu, 0xFF40, 0x93);
+    assert_eq!(mode_lengths(&mut cpu), [20, 46, 48]);
+
+    // Only counted when objects are on
+    write(&mut cpu, 0xFF40, 0x91);
+    assert_eq!(mode_lengths(&mut cpu), [20, 44, 50]);
+}
+
+#[test]
+fn test_fifo_transfer_timing() {
+    // The FIFO renderer works its timing out dot by dot, and lands on the
+    // same lengths
+    let mut cpu = CPU::new(Renderer::Fifo);
+    write(&mut cpu, 0xFF40, 0x91);
+    assert_eq!(mode_lengths(&mut cpu), [20, 43, 51]);
+    write(&mut cpu, 0xFF43, 0x04);
+    assert_eq!(mode_lengths(&mut cpu), [20, 44, 50]);
+
+    write(&mut cpu, 0xFE00, 16);
+    write(&mut cpu, 0xFE01, 0);
+    write(&mut cpu, 0xFF43, 0x01);
+    write(&mut cpu, 0xFF40, 0x93);
+    assert_eq!(mode_lengths(&mut cpu), [20, 46, 48]);
+
+    // The window restarts the fetcher
+    let mut cpu = CPU::new(Renderer::Fifo);
+    write(&mut cpu, 0xFF43, 0x02);
+    write(&mut cpu, 0xFF4B, 7);
+    write(&mut cpu, 0xFF40, 0xB1);
+    assert_eq!(mode_lengths(&mut cpu), [20, 45, 49]);
+}
+
+#[test]
+fn test_ly_counts_lines() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF40, 0x91);
+    for line in 1..154 {
+        assert_eq!(wait_for(&mut cpu, |cpu| read(cpu, 0xFF44) != line - 1), 114);
+        assert_eq!(read(&cpu, 0xFF44), line);
+        assert_eq!(mode(&cpu), if line < 144 { 2 } else { 1 });
+    }
+    tick(&mut cpu, 114);
+    assert_eq!(read(&cpu, 0xFF44), 0);
+
+    // LY is read-only, and turning the LCD off resets it
+    tick(&mut cpu, 114 * 3);
+    write(&mut cpu, 0xFF44, 0x50);
+    assert_eq!(read(&cpu, 0xFF44), 3);
+    write(&mut cpu, 0xFF40, 0x11);
+    assert_eq!(read(&cpu, 0xFF44), 0);
+    assert_eq!(mode(&cpu), 0);
+}
+
+#[test]
+fn test_vblank_interrupt() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF40, 0x91);
+    let cycles = wait_for(&mut cpu, |cpu| requested(cpu, Interrupt::VBlank));
+    assert_eq!(cycles, 144 * 114);
+    assert_eq!(read(&cpu, 0xFF44), 144);
+    assert_eq!(mode(&cpu), 1);
+
+    write(&mut cpu, 0xFF0F, 0x00);
+    let cycles = wait_for(&mut cpu, |cpu| requested(cpu, Interrupt::VBlank));
+    assert_eq!(cycles, 154 * 114);
+}
+
+#[test]
+fn test_lyc_coincidence() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF45, 5);
+    write(&mut cpu, 0xFF41, 0x40);
+    write(&mut cpu, 0xFF40, 0x91);
+    assert_eq!(read(&cpu, 0xFF41) & 0x04, 0x00);
+
+    let cycles = wait_for(&mut cpu, |cpu| requested(cpu, Interrupt::LcdStat));
+    assert_eq!(cycles, 5 * 114);
+    assert_eq!(read(&cpu, 0xFF44), 5);
+    assert_eq!(read(&cpu, 0xFF41) & 0x04, 0x04);
+
+    // The line stays high for the rest of LY 5, so no second request
+    write(&mut cpu, 0xFF0F, 0x00);
+    tick(&mut cpu, 113);
+    assert!(!requested(&cpu, Interrupt::LcdStat));
+    tick(&mut cpu, 1);
+    assert_eq!(read(&cpu, 0xFF41) & 0x04, 0x00);
+}
+
+#[test]
+fn test_lyc_write_requests_interrupt() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF45, 0x20);
+    write(&mut cpu, 0xFF40, 0x91);
+    write(&mut cpu, 0xFF41, 0x40);
+    assert!(!requested(&cpu, Interrupt::LcdStat));
+    write(&mut cpu, 0xFF45, 0x00);
+    assert!(requested(&cpu, Interrupt::LcdStat));
+}
+
+#[test]
+fn test_stat_mode_interrupts() {
+    for (select, expected) in [(0x08, 144), (0x10, 1), (0x20, 144)] {
+        let mut cpu = CPU::default();
+        write(&mut cpu, 0xFF41, select);
+        write(&mut cpu, 0xFF40, 0x91);
+        assert_eq!(
+            count_stat_interrupts(&mut cpu),
+            expected,
+            "STAT {:02X}",
+            select
+        );
+    }
+}
+
+#[test]
+fn test_stat_blocking() {
+    // HBlank runs straight into the next line's OAM scan, so only the
+    // first line after VBlank gets a mode 2 interrupt of its own
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF41, 0x28);
+    write(&mut cpu, 0xFF40, 0x91);
+    assert_eq!(count_stat_interrupts(&mut cpu), 145);
+
+    // And the last HBlank blocks VBlank
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF41, 0x18);
+    write(&mut cpu, 0xFF40, 0x91);
+    assert_eq!(count_stat_interrupts(&mut cpu), 144);
+
+    // LY=LYC is already blocked by the HBlank before it, and then holds
+    // the line high through its own HBlank
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF45, 10);
+    write(&mut cpu, 0xFF41, 0x48);
+    write(&mut cpu, 0xFF40, 0x91);
+    assert_eq!(count_stat_interrupts(&mut cpu), 143);
+}
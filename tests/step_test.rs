@@ -0,0 +1,117 @@
+use yabge::cpu::interrupt::Interrupt;
+use yabge::cpu::registers::Register::{A, HL, PC, SP};
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+#[test]
+fn test_step_returns_cycles() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x00)); // NOP
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x21)); // LD HL, d16
+    cpu.write(Value::SixteenBit(0x0002), Value::SixteenBit(0xABCD));
+    cpu.write(Value::SixteenBit(0x0004), Value::EightBit(0xCB)); // SWAP A
+    cpu.write(Value::SixteenBit(0x0005), Value::EightBit(0x37));
+    cpu.registers.set(A, Value::EightBit(0x12));
+
+    assert_eq!(cpu.step().unwrap(), 1);
+    assert_eq!(cpu.step().unwrap(), 3);
+    assert_eq!(cpu.registers.get(HL), Value::SixteenBit(0xABCD));
+    assert_eq!(cpu.step().unwrap(), 2);
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x21));
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0006));
+    assert_eq!(cpu.clock(), 6);
+}
+
+#[test]
+fn test_step_services_interrupts() {
+    let mut cpu = CPU::default();
+    cpu.set_ime();
+    cpu.registers.set(PC, Value::SixteenBit(0x1234));
+    cpu.registers.set(SP, Value::SixteenBit(0xDFFE));
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x1F));
+    cpu.request_interrupt(Interrupt::VBlank);
+
+    assert_eq!(cpu.step().unwrap(), 5);
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0040));
+}
+
+#[test]
+fn test_step_while_halted() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x76)); // HALT
+    cpu.write(Value::SixteenBit(0xFFFF), Value::EightBit(0x04));
+
+    assert_eq!(cpu.step().unwrap(), 1);
+    assert!(cpu.halted());
+    assert_eq!(cpu.step().unwrap(), 1);
+    assert_eq!(cpu.step().unwrap(), 1);
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0001));
+
+    // Waking up and running the next instruction happen in the same step
+    cpu.request_interrupt(Interrupt::Timer);
+    assert_eq!(cpu.step().unwrap(), 1);
+    assert!(!cpu.halted());
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0002));
+}
+
+#[test]
+fn test_run_for_cycles() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x00)); // NOP
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x21)); // LD HL, d16
+    cpu.write(Value::SixteenBit(0x0002), Value::SixteenBit(0xABCD));
+
+    // The LD overshoots the requested two cycles
+    assert_eq!(cpu.run_for_cycles(2).unwrap(), 4);
+    assert_eq!(cpu.registers.get(PC), Value::SixteenBit(0x0004));
+    assert_eq!(cpu.run_for_cycles(0).unwrap(), 0);
+}
+
+#[test]
+fn test_run_for_cycles_stops_on_stop() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x10)); // STOP
+
+    assert_eq!(cpu.run_for_cycles(100).unwrap(), 1);
+    assert!(cpu.stopped());
+}
+
+#[test]
+fn test_run_until() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x3C)); // INC A
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x18)); // JR s8
+    cpu.write(Value::SixteenBit(0x0002), Value::EightBit(0xFD)); // s8 = -3
+
+    let cycles = cpu
+        .run_until(|cpu| cpu.registers.get(A) == Value::EightBit(0x05))
+        .unwrap();
+
+    assert_eq!(cpu.registers.get(A), Value::EightBit(0x05));
+    assert_eq!(cycles, cpu.clock());
+}
+
+#[test]
+fn test_run_until_stops_on_stop() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0x00)); // NOP
+    cpu.write(Value::SixteenBit(0x0001), Value::EightBit(0x10)); // STOP
+
+    // Would never hold, but STOP ends the run instead of spinning
+    assert_eq!(cpu.run_until(|_| false).unwrap(), 2);
+    assert!(cpu.stopped());
+}
+
+#[test]
+fn test_run_until_gives_up_when_locked() {
+    let mut cpu = CPU::default();
+    cpu.write(Value::SixteenBit(0x0000), Value::EightBit(0xD3)); // Illegal
+
+    // The lockup itself is reported once
+    assert!(cpu.run_until(|_| false).is_err());
+    assert!(cpu.locked());
+    // After that the CPU only idles, so a run ends after one cycle instead
+    // of spinning forever
+    assert_eq!(cpu.run_until(|_| false).unwrap(), 1);
+    assert_eq!(cpu.run_for_cycles(100).unwrap(), 1);
+}
@@ -0,0 +1,150 @@
+use yabge::cpu::interrupt::Interrupt;
+use yabge::cpu::registers::Register::PC;
+use yabge::cpu::value::Value;
+use yabge::cpu::CPU;
+
+mod common;
+
+use common::{read, tick, write};
+
+fn timer_requested(cpu: &CPU) -> bool {
+    read(cpu, 0xFF0F) & Interrupt::Timer.bit() != 0
+}
+
+#[test]
+fn test_div_counts_every_64_cycles() {
+    let mut cpu = CPU::default();
+    tick(&mut cpu, 63);
+    assert_eq!(read(&cpu, 0xFF04), 0x00);
+    tick(&mut cpu, 1);
+    assert_eq!(read(&cpu, 0xFF04), 0x01);
+    tick(&mut cpu, 255 * 64);
+    assert_eq!(read(&cpu, 0xFF04), 0x00);
+}
+
+#[test]
+fn test_div_write_resets_the_whole_divider() {
+    let mut cpu = CPU::default();
+    tick(&mut cpu, 100);
+    write(&mut cpu, 0xFF04, 0x12);
+    assert_eq!(read(&cpu, 0xFF04), 0x00);
+    // The low byte went too, so it's another full 64 cycles
+    tick(&mut cpu, 63);
+    assert_eq!(read(&cpu, 0xFF04), 0x00);
+    tick(&mut cpu, 1);
+    assert_eq!(read(&cpu, 0xFF04), 0x01);
+}
+
+#[test]
+fn test_tima_rates() {
+    // TAC clock select, and the M-cycles between increments
+    for (tac, period) in [(0x04, 256), (0x05, 4), (0x06, 16), (0x07, 64)] {
+        let mut cpu = CPU::default();
+        write(&mut cpu, 0xFF07, tac);
+        tick(&mut cpu, period - 1);
+        assert_eq!(read(&cpu, 0xFF05), 0, "TAC {:02X}", tac);
+        tick(&mut cpu, 1);
+        assert_eq!(read(&cpu, 0xFF05), 1, "TAC {:02X}", tac);
+        tick(&mut cpu, 9 * period);
+        assert_eq!(read(&cpu, 0xFF05), 10, "TAC {:02X}", tac);
+    }
+}
+
+#[test]
+fn test_tima_disabled() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF07, 0x01);
+    tick(&mut cpu, 100);
+    assert_eq!(read(&cpu, 0xFF05), 0);
+}
+
+#[test]
+fn test_tima_overflow_reload_delay() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF06, 0xAB);
+    write(&mut cpu, 0xFF05, 0xFF);
+    write(&mut cpu, 0xFF07, 0x05);
+
+    tick(&mut cpu, 4);
+    // TIMA sits at 0 for a cycle before TMA is loaded
+    assert_eq!(read(&cpu, 0xFF05), 0x00);
+    assert!(!timer_requested(&cpu));
+    tick(&mut cpu, 1);
+    assert_eq!(read(&cpu, 0xFF05), 0xAB);
+    assert!(timer_requested(&cpu));
+}
+
+#[test]
+fn test_tima_write_cancels_reload() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF06, 0xAB);
+    write(&mut cpu, 0xFF05, 0xFF);
+    write(&mut cpu, 0xFF07, 0x05);
+
+    tick(&mut cpu, 4);
+    write(&mut cpu, 0xFF05, 0x42);
+    tick(&mut cpu, 1);
+    assert_eq!(read(&cpu, 0xFF05), 0x42);
+    assert!(!timer_requested(&cpu));
+}
+
+#[test]
+fn test_writes_while_reloading() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF06, 0xAB);
+    write(&mut cpu, 0xFF05, 0xFF);
+    write(&mut cpu, 0xFF07, 0x05);
+    tick(&mut cpu, 5);
+
+    // On the reload cycle TMA wins over a TIMA write, and a TMA write goes
+    // straight through to TIMA
+    write(&mut cpu, 0xFF05, 0x42);
+    assert_eq!(read(&cpu, 0xFF05), 0xAB);
+    write(&mut cpu, 0xFF06, 0x11);
+    assert_eq!(read(&cpu, 0xFF05), 0x11);
+
+    tick(&mut cpu, 1);
+    write(&mut cpu, 0xFF05, 0x42);
+    assert_eq!(read(&cpu, 0xFF05), 0x42);
+}
+
+#[test]
+fn test_falling_edge_from_div_write() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF07, 0x05);
+    // Divider bit 3 is set halfway through each period
+    tick(&mut cpu, 2);
+    assert_eq!(read(&cpu, 0xFF05), 0);
+    write(&mut cpu, 0xFF04, 0x00);
+    assert_eq!(read(&cpu, 0xFF05), 1);
+
+    // Before bit 3 is set, nothing happens
+    tick(&mut cpu, 1);
+    write(&mut cpu, 0xFF04, 0x00);
+    assert_eq!(read(&cpu, 0xFF05), 1);
+}
+
+#[test]
+fn test_falling_edge_from_tac_write() {
+    let mut cpu = CPU::default();
+    write(&mut cpu, 0xFF07, 0x05);
+    tick(&mut cpu, 2);
+    // Turning the timer off drops the signal
+    write(&mut cpu, 0xFF07, 0x01);
+    assert_eq!(read(&cpu, 0xFF05), 1);
+}
+
+#[test]
+fn test_timer_interrupt_is_serviced() {
+    let mut cpu = CPU::default();
+    cpu.set_ime();
+    write(&mut cpu, 0xFFFF, Interrupt::Timer.bit());
+    write(&mut cpu, 0xFF05, 0xFF);
+    write(&mut cpu, 0xFF07, 0x05);
+
+    // NOPs from 0x0000 until the interrupt fires
+    let cycles = cpu
+        .run_until(|cpu| cpu.registers.get(PC) == Value::SixteenBit(0x0050))
+        .unwrap();
+    assert!(cycles <= 16);
+}